@@ -5,6 +5,13 @@ pub enum DecodeError {
     InvalidCharacter(char),
     InvalidLength,
     NoSeparator,
+    InvalidLabel {
+        expected: &'static str,
+        found: String,
+    },
+    MissingLabel {
+        expected: &'static str,
+    },
 }
 
 impl Display for DecodeError {
@@ -17,6 +24,12 @@ impl Display for DecodeError {
                 write!(f, "Invalid length for decoding sid, expected 27 characters")
             }
             DecodeError::NoSeparator => write!(f, "No separator while decoding sid"),
+            DecodeError::InvalidLabel { expected, found } => {
+                write!(f, "Invalid label while decoding sid: expected {:?}, found {:?}", expected, found)
+            }
+            DecodeError::MissingLabel { expected } => {
+                write!(f, "Missing label while decoding sid: expected a {:?} prefix", expected)
+            }
         }
     }
 }
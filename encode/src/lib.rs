@@ -22,8 +22,13 @@ pub const SHORT_LENGTH: usize = 22;
 // really in range 0..32 (or 255 if error)
 #[inline]
 fn lookup(value: u8) -> u8 {
-    let value = value % LOOKUP_TABLE_LENGTH;
-    REVERSE_ALPHABET[value as usize]
+    let decoded = REVERSE_ALPHABET[(value % LOOKUP_TABLE_LENGTH) as usize];
+    // the table is a hash, so characters outside the alphabet can collide with ones inside it.
+    if decoded < 32 && ALPHABET[decoded as usize] as u8 == value {
+        decoded
+    } else {
+        255
+    }
 }
 
 pub fn base32_decode(input: &str) -> Result<[u8; 16], DecodeError> {
@@ -31,7 +36,7 @@ pub fn base32_decode(input: &str) -> Result<[u8; 16], DecodeError> {
         return Err(DecodeError::InvalidLength);
     }
     let input: [u8; 27] = input.as_bytes().try_into().unwrap();
-    if !input[22] == b'_' {
+    if input[22] != b'_' {
         return Err(DecodeError::NoSeparator);
     }
 
@@ -60,6 +65,10 @@ pub fn base32_decode(input: &str) -> Result<[u8; 16], DecodeError> {
         let c = input[idx255];
         return Err(DecodeError::InvalidCharacter(c as char));
     }
+    // the first character only carries the top 3 bits.
+    if intermediate[0] > 7 {
+        return Err(DecodeError::InvalidCharacter(input[0] as char));
+    }
 
     let mut result = [0u8; 16];
     // now we can do the actual decoding.
@@ -190,6 +199,18 @@ mod test {
     //     }
     // }
 
+    #[test]
+    fn test_invalid() {
+        assert!(base32_decode("01081g81860w40j2gb1g6g_w3rg").is_ok());
+        assert!(matches!(base32_decode("01081g81860w40j2gb1g6g_w3r"), Err(DecodeError::InvalidLength)));
+        assert!(matches!(base32_decode("01081g81860w40j2gb1g6gxw3rg"), Err(DecodeError::NoSeparator)));
+        // characters that collide with the alphabet in the lookup table
+        assert!(matches!(base32_decode("01081G81860w40j2gb1g6g_w3rg"), Err(DecodeError::InvalidCharacter('G'))));
+        assert!(matches!(base32_decode("01081g81860w40j2gb1g6g_w3r-"), Err(DecodeError::InvalidCharacter('-'))));
+        assert!(matches!(base32_decode("81081g81860w40j2gb1g6g_w3rg"), Err(DecodeError::InvalidCharacter('8'))));
+        assert!(base32_decode("7zzzzzzzzzzzzzzzzzzzzz_zzzz").is_ok());
+    }
+
     #[test]
     fn test_rand() {
         use rand::RngCore;
@@ -211,9 +232,9 @@ mod test {
             assert_eq!(dec, t);
 
             if s < t {
-                assert_eq!(s_enc < t_enc, true);
+                assert!(s_enc < t_enc);
             } else {
-                assert_eq!(s_enc > t_enc, true);
+                assert!(s_enc > t_enc);
             }
         }
     }
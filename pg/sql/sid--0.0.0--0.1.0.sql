@@ -0,0 +1,13 @@
+-- Binary I/O for the sid type, used by drivers like sqlx (`sid::PgSid`). Installs created before 0.1.0 lack it,
+-- so run `ALTER EXTENSION sid UPDATE;` after installing the new build.
+CREATE FUNCTION "sid_send"("sid" sid) RETURNS bytea
+IMMUTABLE STRICT PARALLEL SAFE
+LANGUAGE c /* Rust */
+AS 'MODULE_PATHNAME', 'sid_send_wrapper';
+
+CREATE FUNCTION "sid_recv"("internal" internal) RETURNS sid
+IMMUTABLE STRICT PARALLEL SAFE
+LANGUAGE c /* Rust */
+AS 'MODULE_PATHNAME', 'sid_recv_wrapper';
+
+ALTER TYPE sid SET (SEND = sid_send, RECEIVE = sid_recv);
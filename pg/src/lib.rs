@@ -253,6 +253,48 @@ fn sid_from_uuid(uuid: pgx::Uuid, label: &str) -> Sid {
     Sid::new(data, label)
 }
 
+// Binary wire format, used by drivers like sqlx: the 16 data bytes, followed by the label (if any).
+#[pg_extern(immutable, parallel_safe)]
+fn sid_send(sid: Sid) -> Vec<u8> {
+    let mut bytes = sid.data.to_vec();
+    if let Some(label) = sid.label {
+        let label = label.0.get().to_le_bytes();
+        bytes.extend(label.iter().take_while(|&&c| c != 0));
+    }
+    bytes
+}
+
+#[pg_extern(immutable, parallel_safe)]
+fn sid_recv(internal: pgx::Internal) -> Sid {
+    let buf = unsafe { internal.get_mut::<pg_sys::StringInfoData>() }
+        .expect("sid_recv called without a buffer");
+    let bytes = unsafe {
+        let remaining = (buf.len - buf.cursor) as usize;
+        std::slice::from_raw_parts(buf.data.add(buf.cursor as usize) as *const u8, remaining)
+    };
+    buf.cursor = buf.len;
+    if bytes.len() < 16 || bytes.len() > 16 + MAX_LABEL_LENGTH {
+        error!("invalid sid length: {}", bytes.len());
+    }
+    // the label has to survive `output` and `input`, e.g. through pg_dump.
+    let label = &bytes[16..];
+    if label.iter().any(|&c| !c.is_ascii() || c == 0 || c == b'_') {
+        error!("invalid sid label: expected 1 to {} ascii characters without '_'", MAX_LABEL_LENGTH);
+    }
+    let mut data = [0u8; 16];
+    data.copy_from_slice(&bytes[..16]);
+    Sid { data, label: Label::from_bytes(label) }
+}
+
+// Attach the binary I/O functions once both exist. `ALTER TYPE ... SET` needs pg13 or newer.
+extension_sql!(
+    r#"
+ALTER TYPE sid SET (SEND = sid_send, RECEIVE = sid_recv);
+"#,
+    name = "sid_binary_io",
+    requires = [Sid, sid_send, sid_recv],
+);
+
 #[cfg(any(test, feature = "pg_test"))]
 #[pg_schema]
 mod tests {
@@ -265,6 +307,62 @@ mod tests {
         assert!(sid.data.iter().all(|&x| x == 0));
         assert_eq!(sid.label, Label::new("test"));
     }
+
+    #[pg_test]
+    fn test_sid_send() {
+        let sid = sid_null("test");
+        let bytes = sid_send(sid);
+        assert_eq!(bytes.len(), 16 + 4);
+        assert_eq!(&bytes[16..], b"test");
+    }
+
+    fn recv(bytes: &[u8]) -> Sid {
+        let mut buf = StringInfo::new();
+        buf.push_bytes(bytes);
+        sid_recv(pgx::Internal::from(Some(pg_sys::Datum::from(buf.into_pg()))))
+    }
+
+    #[pg_test]
+    fn test_sid_send_recv() {
+        let sid = sid_new("usr");
+        let roundtrip = recv(&sid_send(sid));
+        assert_eq!(roundtrip.data, sid.data);
+        assert_eq!(roundtrip.label, Label::new("usr"));
+
+        let sid = Sid { data: sid.data, label: None };
+        let roundtrip = recv(&sid_send(sid));
+        assert_eq!(roundtrip.data, sid.data);
+        assert_eq!(roundtrip.label, None);
+    }
+
+    #[pg_test(error = "invalid sid label: expected 1 to 8 ascii characters without '_'")]
+    fn test_sid_recv_rejects_underscore() {
+        let mut bytes = [0u8; 16].to_vec();
+        bytes.extend(b"us_r");
+        recv(&bytes);
+    }
+
+    #[pg_test(error = "invalid sid label: expected 1 to 8 ascii characters without '_'")]
+    fn test_sid_recv_rejects_nul() {
+        let mut bytes = [0u8; 16].to_vec();
+        bytes.extend(b"us\0r");
+        recv(&bytes);
+    }
+
+    #[pg_test(error = "invalid sid label: expected 1 to 8 ascii characters without '_'")]
+    fn test_sid_recv_rejects_non_ascii() {
+        let mut bytes = [0u8; 16].to_vec();
+        bytes.extend("usé".as_bytes());
+        recv(&bytes);
+    }
+
+    #[pg_test]
+    fn test_sid_binary_io_registered() {
+        let send = Spi::get_one::<String>("SELECT typsend::text FROM pg_type WHERE oid = 'sid'::regtype");
+        assert_eq!(send.unwrap(), Some("sid_send".to_string()));
+        let recv = Spi::get_one::<String>("SELECT typreceive::text FROM pg_type WHERE oid = 'sid'::regtype");
+        assert_eq!(recv.unwrap(), Some("sid_recv".to_string()));
+    }
 }
 
 /// This module is required by `cargo pgx test` invocations. 
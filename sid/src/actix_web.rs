@@ -0,0 +1,86 @@
+#![doc = crate::extract::extractor_docs!("actix-web")]
+
+use std::future::{ready, Ready};
+
+use actix_web::dev::Payload;
+use actix_web::error::{InternalError, PathError, QueryPayloadError};
+use actix_web::{web, Error, FromRequest, HttpRequest, HttpResponse};
+use serde::de::DeserializeOwned;
+
+use crate::extract::{error_body, extractors};
+
+extractors!("actix_web::web::Path", "actix_web::web::Query");
+
+fn bad_request(error: Error) -> Error {
+    // Without a `PathConfig`, actix-web wraps the path error in a 404.
+    let message = if let Some(e) = error.as_error::<InternalError<serde::de::value::Error>>() {
+        e.to_string()
+    } else if let Some(PathError::Deserialize(e)) = error.as_error::<PathError>() {
+        e.to_string()
+    } else if let Some(QueryPayloadError::Deserialize(e)) = error.as_error::<QueryPayloadError>() {
+        e.to_string()
+    } else {
+        // e.g. the app configured its own error handler
+        return error;
+    };
+    let response = HttpResponse::BadRequest().json(error_body(&message));
+    InternalError::from_response(message, response).into()
+}
+
+impl<T: DeserializeOwned> FromRequest for Path<T> {
+    type Error = Error;
+    type Future = Ready<Result<Self, Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let result = web::Path::<T>::from_request(req, payload).into_inner();
+        ready(result.map(|path| Path(path.into_inner())).map_err(bad_request))
+    }
+}
+
+impl<T: DeserializeOwned> FromRequest for Query<T> {
+    type Error = Error;
+    type Future = Ready<Result<Self, Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let result = web::Query::<T>::from_request(req, payload).into_inner();
+        ready(result.map(|query| Query(query.into_inner())).map_err(bad_request))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extract::tests::{check_path, check_query, Params, User};
+    use crate::Sid;
+    use actix_web::test::{call_service, init_service, read_body, TestRequest};
+    use actix_web::App;
+
+    async fn get_uri(uri: String) -> (u16, String) {
+        let app = init_service(
+            App::new()
+                .route(
+                    "/users/{id}",
+                    web::get().to(|Path(id): Path<Sid<User>>| async move { id.to_string() }),
+                )
+                .route(
+                    "/users",
+                    web::get().to(|Query(params): Query<Params>| async move { params.id.to_string() }),
+                ),
+        )
+        .await;
+        let response = call_service(&app, TestRequest::get().uri(&uri).to_request()).await;
+        let status = response.status().as_u16();
+        let body = read_body(response).await;
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[actix_rt::test]
+    async fn test_path() {
+        check_path(get_uri).await;
+    }
+
+    #[actix_rt::test]
+    async fn test_query() {
+        check_query(get_uri).await;
+    }
+}
@@ -0,0 +1,81 @@
+use std::borrow::Cow;
+
+use async_graphql::{InputValueError, InputValueResult, Scalar, ScalarType, TypeName, Value};
+
+use crate::{decode_labeled, schema_name, Label, Sid};
+
+impl<T: Label + Send + Sync> TypeName for Sid<T> {
+    fn type_name() -> Cow<'static, str> {
+        schema_name::<T>()
+    }
+}
+
+/// A sid in its labeled form, e.g. usr_0da0fa0e02cssbhkanf04c_srb0. Input may also omit the label, but a label that
+/// doesn't match the type is rejected.
+#[Scalar(name_type)]
+impl<T: Label + Send + Sync> ScalarType for Sid<T> {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::String(s) => decode_labeled::<T>(s)
+                .map(Sid::from)
+                .map_err(InputValueError::custom),
+            _ => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    // Only checks the type, so that `parse` can report the detailed error.
+    fn is_valid(value: &Value) -> bool {
+        matches!(value, Value::String(_))
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(format!("{:?}", self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{label, Label, Sid};
+    use async_graphql::{EmptyMutation, EmptySubscription, Object, Request, Schema, Variables};
+
+    label!(User, "usr");
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn user(&self, id: Sid<User>) -> Sid<User> {
+            id
+        }
+    }
+
+    fn schema() -> Schema<Query, EmptyMutation, EmptySubscription> {
+        Schema::new(Query, EmptyMutation, EmptySubscription)
+    }
+
+    #[tokio::test]
+    async fn test_scalar() {
+        let sid = Sid::<User>::new();
+        let request = Request::new("query($id: Sid_usr!) { user(id: $id) }")
+            .variables(Variables::from_json(serde_json::json!({ "id": sid.to_string() })));
+        let response = schema().execute(request).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        assert_eq!(
+            response.data.into_json().unwrap(),
+            serde_json::json!({ "user": format!("{:?}", sid) })
+        );
+
+        let response = schema()
+            .execute(format!("{{ user(id: \"team_{}\") }}", sid))
+            .await;
+        assert_eq!(response.errors.len(), 1);
+        assert!(response.errors[0].message.contains("Invalid label"), "{}", response.errors[0].message);
+    }
+
+    #[test]
+    fn test_sdl() {
+        let sdl = schema().sdl();
+        assert!(sdl.contains("scalar Sid_usr"), "{}", sdl);
+        assert!(sdl.contains("A sid in its labeled form"), "{}", sdl);
+    }
+}
@@ -0,0 +1,105 @@
+#![doc = crate::extract::extractor_docs!("axum")]
+
+use axum::extract::path::ErrorKind;
+use axum::extract::rejection::{PathRejection, QueryRejection};
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::de::DeserializeOwned;
+
+use crate::extract::{error_body, extractors};
+
+extractors!("axum::extract::Path", "axum::extract::Query");
+
+fn bad_request(message: String) -> Response {
+    (StatusCode::BAD_REQUEST, Json(error_body(&message))).into_response()
+}
+
+/// The error our deserializer produced, without the field path axum prefixes it with.
+fn query_message(error: &(dyn std::error::Error + 'static)) -> String {
+    let mut error = error;
+    loop {
+        if let Some(e) = error.downcast_ref::<serde_path_to_error::Error<serde::de::value::Error>>() {
+            return e.inner().to_string();
+        }
+        match error.source() {
+            Some(source) => error = source,
+            None => return error.to_string(),
+        }
+    }
+}
+
+impl<T, S> FromRequestParts<S> for Path<T>
+where
+    T: DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        match axum::extract::Path::<T>::from_request_parts(parts, state).await {
+            Ok(axum::extract::Path(value)) => Ok(Path(value)),
+            Err(PathRejection::FailedToDeserializePathParams(e)) if e.status() == StatusCode::BAD_REQUEST => {
+                Err(bad_request(match e.into_kind() {
+                    ErrorKind::DeserializeError { message, .. } | ErrorKind::Message(message) => message,
+                    kind => kind.to_string(),
+                }))
+            }
+            Err(rejection) => Err(rejection.into_response()),
+        }
+    }
+}
+
+impl<T, S> FromRequestParts<S> for Query<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        match axum::extract::Query::<T>::from_request_parts(parts, state).await {
+            Ok(axum::extract::Query(value)) => Ok(Query(value)),
+            Err(QueryRejection::FailedToDeserializeQueryString(e)) => Err(bad_request(query_message(&e))),
+            Err(rejection) => Err(rejection.into_response()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extract::tests::{check_path, check_query, Params, User};
+    use crate::Sid;
+    use axum::body::{to_bytes, Body};
+    use axum::http::Request;
+    use axum::routing::get;
+    use axum::Router;
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        Router::new()
+            .route("/users/{id}", get(|Path(id): Path<Sid<User>>| async move { id.to_string() }))
+            .route("/users", get(|Query(params): Query<Params>| async move { params.id.to_string() }))
+    }
+
+    async fn get_uri(uri: String) -> (u16, String) {
+        let request = Request::get(uri).body(Body::empty()).unwrap();
+        let response = app().oneshot(request).await.unwrap();
+        let status = response.status().as_u16();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_path() {
+        check_path(get_uri).await;
+    }
+
+    #[tokio::test]
+    async fn test_query() {
+        check_query(get_uri).await;
+    }
+}
@@ -0,0 +1,41 @@
+use bincode::de::{BorrowDecoder, Decoder};
+use bincode::enc::Encoder;
+use bincode::error::{DecodeError, EncodeError};
+use bincode::{BorrowDecode, Decode, Encode};
+
+use crate::Sid;
+
+impl<T> Encode for Sid<T> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.data.encode(encoder)
+    }
+}
+
+impl<Context, T> Decode<Context> for Sid<T> {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        <[u8; 16]>::decode(decoder).map(Sid::from)
+    }
+}
+
+impl<'de, Context, T> BorrowDecode<'de, Context> for Sid<T> {
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        <[u8; 16]>::borrow_decode(decoder).map(Sid::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{label, Label, Sid};
+
+    label!(User, "usr");
+
+    #[test]
+    fn test_round_trip() {
+        let config = bincode::config::standard();
+        let sid = Sid::<User>::new();
+        let bytes = bincode::encode_to_vec(sid, config).unwrap();
+        assert_eq!(&bytes, sid.data());
+        let (decoded, len): (Sid<User>, _) = bincode::decode_from_slice(&bytes, config).unwrap();
+        assert_eq!((decoded, len), (sid, 16));
+    }
+}
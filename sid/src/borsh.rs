@@ -0,0 +1,34 @@
+use std::io::{Read, Result, Write};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::Sid;
+
+impl<T> BorshSerialize for Sid<T> {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        writer.write_all(&self.data)
+    }
+}
+
+impl<T> BorshDeserialize for Sid<T> {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        let mut data = [0u8; 16];
+        reader.read_exact(&mut data)?;
+        Ok(Sid::from(data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{label, Label, Sid};
+
+    label!(User, "usr");
+
+    #[test]
+    fn test_round_trip() {
+        let sid = Sid::<User>::new();
+        let bytes = borsh::to_vec(&sid).unwrap();
+        assert_eq!(&bytes, sid.data());
+        assert_eq!(borsh::from_slice::<Sid<User>>(&bytes).unwrap(), sid);
+    }
+}
@@ -0,0 +1,113 @@
+//! Conversions to BSON. For struct fields, use `#[serde(with = "sid::serde::as_bson")]`, which goes through these.
+use ::bson::spec::BinarySubtype;
+use ::bson::{Binary, Bson, Uuid};
+
+use crate::{decode_labeled, Label, Sid};
+
+impl<T> From<Sid<T>> for Uuid {
+    fn from(sid: Sid<T>) -> Self {
+        Uuid::from_bytes(sid.data)
+    }
+}
+
+impl<T> From<Uuid> for Sid<T> {
+    fn from(uuid: Uuid) -> Self {
+        Sid::from(uuid.bytes())
+    }
+}
+
+/// Binary subtype 4 (UUID).
+impl<T> From<Sid<T>> for Binary {
+    fn from(sid: Sid<T>) -> Self {
+        Binary {
+            subtype: BinarySubtype::Uuid,
+            bytes: sid.data.to_vec(),
+        }
+    }
+}
+
+impl<T> From<Sid<T>> for Bson {
+    fn from(sid: Sid<T>) -> Self {
+        Bson::Binary(sid.into())
+    }
+}
+
+/// Accepts binary subtype 4, or subtype 0 with 16 bytes, and strings: the labeled or bare form, or a
+/// hyphenated uuid.
+pub(crate) fn from_bson<T: Label>(value: &Bson) -> Result<Sid<T>, String> {
+    match value {
+        Bson::Binary(Binary {
+            subtype: BinarySubtype::Uuid | BinarySubtype::Generic,
+            bytes,
+        }) => Sid::try_from(bytes.as_slice()).map_err(|e| e.to_string()),
+        Bson::String(s) if s.len() == 36 => Uuid::parse_str(s).map(Sid::from).map_err(|e| e.to_string()),
+        Bson::String(s) => decode_labeled::<T>(s).map(Sid::from).map_err(|e| e.to_string()),
+        other => Err(format!("expected a sid, found {:?}", other.element_type())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::bson::{doc, Document};
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::label;
+
+    label!(User, "usr");
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Record {
+        #[serde(rename = "_id", with = "crate::serde::as_bson")]
+        id: Sid<User>,
+        #[serde(default, with = "crate::serde::as_bson::option")]
+        parent: Option<Sid<User>>,
+    }
+
+    #[test]
+    fn test_binary_subtype() {
+        let sid = Sid::<User>::new();
+        let record = Record { id: sid, parent: Some(Sid::null()) };
+
+        let document = ::bson::to_document(&record).unwrap();
+        let Some(Bson::Binary(binary)) = document.get("_id") else {
+            panic!("{:?}", document);
+        };
+        assert_eq!(binary.subtype, BinarySubtype::Uuid);
+        assert_eq!(binary.bytes, sid.data());
+        assert_eq!(::bson::from_document::<Record>(document).unwrap(), record);
+
+        let bytes = ::bson::to_vec(&record).unwrap();
+        let raw = ::bson::RawDocumentBuf::from_bytes(bytes.clone()).unwrap();
+        assert_eq!(raw.get_binary("_id").unwrap().subtype, BinarySubtype::Uuid);
+        assert_eq!(::bson::from_slice::<Record>(&bytes).unwrap(), record);
+    }
+
+    #[test]
+    fn test_deserialize_other_forms() {
+        let sid = Sid::<User>::new();
+        let documents: [Document; 4] = [
+            doc! { "_id": Binary { subtype: BinarySubtype::Generic, bytes: sid.data().to_vec() } },
+            doc! { "_id": format!("{:?}", sid) },
+            doc! { "_id": sid.to_string() },
+            doc! { "_id": Uuid::from(sid).to_string() },
+        ];
+        for document in documents {
+            let record: Record = ::bson::from_document(document).unwrap();
+            assert_eq!(record.id, sid);
+        }
+
+        let short = doc! { "_id": Binary { subtype: BinarySubtype::Generic, bytes: vec![0; 15] } };
+        assert!(::bson::from_document::<Record>(short).is_err());
+        let wrong = doc! { "_id": Binary { subtype: BinarySubtype::Md5, bytes: sid.data().to_vec() } };
+        assert!(::bson::from_document::<Record>(wrong).is_err());
+    }
+
+    #[test]
+    fn test_sorts_by_creation() {
+        let a = Sid::<User>::new();
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        let b = Sid::<User>::new();
+        assert!(Binary::from(a).bytes < Binary::from(b).bytes);
+    }
+}
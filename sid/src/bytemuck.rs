@@ -0,0 +1,29 @@
+use bytemuck::{Pod, TransparentWrapper, Zeroable};
+
+use crate::Sid;
+
+// SAFETY: Sid is repr(transparent) over [u8; 16], and any 16 bytes are a valid sid.
+unsafe impl<T> Zeroable for Sid<T> {}
+
+unsafe impl<T: 'static> Pod for Sid<T> {}
+
+unsafe impl<T> TransparentWrapper<[u8; 16]> for Sid<T> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{label, Label, Sid};
+
+    label!(User, "usr");
+
+    #[test]
+    fn test_cast_slice() {
+        let sids = [Sid::<User>::new(), Sid::<User>::new()];
+        let bytes: &[u8] = bytemuck::cast_slice(&sids);
+        assert_eq!(bytes.len(), 32);
+        assert_eq!(&bytes[..16], sids[0].data());
+        let cast: &[Sid<User>] = bytemuck::cast_slice(bytes);
+        assert_eq!(cast, &sids);
+        assert!(bytemuck::try_cast_slice::<u8, Sid<User>>(&bytes[1..]).is_err());
+        assert_eq!(<Sid<User> as bytemuck::Zeroable>::zeroed(), Sid::null());
+    }
+}
@@ -0,0 +1,175 @@
+use std::fmt::{Debug, Display, Formatter};
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use aes::cipher::{BlockDecrypt, BlockEncrypt, KeyInit};
+use aes::Aes128;
+use sid_encode::base32_encode;
+
+use crate::{decode_labeled, DecodeError, Label, NoLabel, Sid};
+
+/// Keyed permutation of the 128 bits of a sid (AES-128 over the raw bytes). Encrypted ids
+/// look like regular sids but no longer leak their creation timestamp.
+#[derive(Clone)]
+pub struct SidCipher {
+    cipher: Aes128,
+}
+
+impl SidCipher {
+    pub fn new(key: [u8; 16]) -> Self {
+        Self {
+            cipher: Aes128::new(&key.into()),
+        }
+    }
+
+    pub fn encrypt<T>(&self, sid: Sid<T>) -> ObfuscatedSid<T> {
+        let mut block = sid.data.into();
+        self.cipher.encrypt_block(&mut block);
+        ObfuscatedSid::from(<[u8; 16]>::from(block))
+    }
+
+    pub fn decrypt<T>(&self, id: ObfuscatedSid<T>) -> Sid<T> {
+        let mut block = id.data.into();
+        self.cipher.decrypt_block(&mut block);
+        Sid::from(<[u8; 16]>::from(block))
+    }
+}
+
+impl Debug for SidCipher {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SidCipher")
+    }
+}
+
+/// The public, opaque form of a `Sid<T>`. Formats with its label, e.g. usr_3xq0...
+pub struct ObfuscatedSid<T = NoLabel> {
+    data: [u8; 16],
+    marker: PhantomData<T>,
+}
+
+impl<T> ObfuscatedSid<T> {
+    pub fn data(&self) -> &[u8; 16] {
+        &self.data
+    }
+}
+
+impl<T> From<[u8; 16]> for ObfuscatedSid<T> {
+    fn from(data: [u8; 16]) -> Self {
+        Self {
+            data,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Clone for ObfuscatedSid<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ObfuscatedSid<T> {}
+
+impl<T> PartialEq for ObfuscatedSid<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data
+    }
+}
+
+impl<T> Eq for ObfuscatedSid<T> {}
+
+impl<T> std::hash::Hash for ObfuscatedSid<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.data.hash(state)
+    }
+}
+
+impl<T: Label> Display for ObfuscatedSid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let label = T::label();
+        if !label.is_empty() {
+            f.write_str(label)?;
+            f.write_str("_")?;
+        }
+        f.write_str(&base32_encode(self.data))
+    }
+}
+
+impl<T: Label> Debug for ObfuscatedSid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl<T: Label> FromStr for ObfuscatedSid<T> {
+    type Err = DecodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        decode_labeled::<T>(s).map(Self::from)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: Label> serde::Serialize for ObfuscatedSid<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Label> serde::Deserialize<'de> for ObfuscatedSid<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::label;
+
+    label!(User, "usr");
+    label!(Team, "team");
+
+    #[test]
+    fn test_round_trip() {
+        let cipher = SidCipher::new(*b"0123456789abcdef");
+        let sid = Sid::<User>::new();
+        let obfuscated = cipher.encrypt(sid);
+        assert_ne!(obfuscated.data(), sid.data());
+        assert_eq!(cipher.decrypt(obfuscated), sid);
+
+        let other = SidCipher::new(*b"fedcba9876543210");
+        assert_ne!(other.decrypt(obfuscated), sid);
+    }
+
+    #[test]
+    fn test_hides_timestamp() {
+        let cipher = SidCipher::new([7; 16]);
+        let a = cipher.encrypt(Sid::<User>::from([0; 16]));
+        let b = cipher.encrypt(Sid::<User>::from([0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]));
+        assert_ne!(a.data()[..6], b.data()[..6]);
+    }
+
+    #[test]
+    fn test_format() {
+        let cipher = SidCipher::new([7; 16]);
+        let obfuscated = cipher.encrypt(Sid::<User>::new());
+        let s = obfuscated.to_string();
+        assert!(s.starts_with("usr_"));
+        assert_eq!(s.len(), 4 + 27);
+        assert_eq!(s.parse::<ObfuscatedSid<User>>().unwrap(), obfuscated);
+        assert_eq!(s[4..].parse::<ObfuscatedSid<User>>().unwrap(), obfuscated);
+        assert!(matches!(
+            s.parse::<ObfuscatedSid<Team>>(),
+            Err(DecodeError::InvalidLabel { .. })
+        ));
+    }
+}
@@ -0,0 +1,124 @@
+//! `ValueParserFactory` for `Sid<T>`, so `#[arg] user: Sid<User>` works in derive-based CLIs.
+use std::ffi::OsStr;
+use std::fmt;
+use std::marker::PhantomData;
+
+use clap::builder::{NonEmptyStringValueParser, TypedValueParser, ValueParserFactory};
+use clap::{Arg, Command};
+
+use crate::{decode_labeled, DecodeError, Label, Sid};
+
+/// Parses the labeled or bare form, or a hyphenated uuid. A mismatched label is an error.
+pub struct SidValueParser<T>(PhantomData<fn() -> T>);
+
+impl<T> SidValueParser<T> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T> Default for SidValueParser<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for SidValueParser<T> {
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
+
+impl<T> fmt::Debug for SidValueParser<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SidValueParser")
+    }
+}
+
+impl<T: Label + Send + Sync + 'static> TypedValueParser for SidValueParser<T> {
+    type Value = Sid<T>;
+
+    fn parse_ref(&self, cmd: &Command, arg: Option<&Arg>, value: &OsStr) -> Result<Self::Value, clap::Error> {
+        NonEmptyStringValueParser::new()
+            .try_map(|s| parse::<T>(&s))
+            .parse_ref(cmd, arg, value)
+    }
+}
+
+impl<T: Label + Send + Sync + 'static> ValueParserFactory for Sid<T> {
+    type Parser = SidValueParser<T>;
+
+    fn value_parser() -> Self::Parser {
+        SidValueParser::new()
+    }
+}
+
+fn parse<T: Label>(s: &str) -> Result<Sid<T>, String> {
+    if s.len() == 36 {
+        return uuid::Uuid::parse_str(s)
+            .map(Sid::from)
+            .map_err(|e| format!("not a valid uuid: {}", e));
+    }
+    decode_labeled::<T>(s).map(Sid::from).map_err(|e| match e {
+        DecodeError::InvalidLabel { expected, found } => {
+            format!("expected a {expected} id, like {:?}, but found a {found} id", Sid::<T>::null())
+        }
+        DecodeError::InvalidLength | DecodeError::NoSeparator if T::label().is_empty() => {
+            "expected a sid (27 chars, e.g. 0da0fa0e02cssbhkanf04c_srb0) or a uuid".to_string()
+        }
+        DecodeError::InvalidLength | DecodeError::NoSeparator => {
+            format!("expected a {} sid, like {:?}, or a uuid", T::label(), Sid::<T>::null())
+        }
+        e => e.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::error::ErrorKind;
+    use clap::Parser;
+
+    use crate::{label, Label, Sid};
+
+    label!(User, "usr");
+    label!(Team, "team");
+
+    #[derive(Parser, Debug)]
+    struct Cli {
+        user: Sid<User>,
+        #[arg(long)]
+        team: Option<Sid<Team>>,
+    }
+
+    #[test]
+    fn test_forms() {
+        let sid = Sid::<User>::new();
+        for arg in [format!("{:?}", sid), sid.to_string(), sid.uuid().to_string()] {
+            let cli = Cli::try_parse_from(["cli", &arg]).unwrap();
+            assert_eq!(cli.user, sid);
+            assert_eq!(cli.team, None);
+        }
+
+        let team = Sid::<Team>::new();
+        let cli = Cli::try_parse_from(["cli", &sid.to_string(), "--team", &format!("{:?}", team)]).unwrap();
+        assert_eq!(cli.team, Some(team));
+    }
+
+    #[test]
+    fn test_errors() {
+        let team = Sid::<Team>::new();
+        let err = Cli::try_parse_from(["cli", &format!("{:?}", team)]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ValueValidation);
+        let message = err.to_string();
+        assert!(message.contains("<USER>"), "{}", message);
+        assert!(message.contains("expected a usr id"), "{}", message);
+        assert!(message.contains("found a team id"), "{}", message);
+
+        let err = Cli::try_parse_from(["cli", "usr_123"]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ValueValidation);
+        assert!(err.to_string().contains("expected a usr sid"), "{}", err);
+
+        let err = Cli::try_parse_from(["cli", "01020304-0506-0708-090a-0b0c0d0e0fxx"]).unwrap_err();
+        assert!(err.to_string().contains("not a valid uuid"), "{}", err);
+    }
+}
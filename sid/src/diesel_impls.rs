@@ -0,0 +1,214 @@
+use std::io::Write;
+
+use diesel::backend::Backend;
+use diesel::deserialize::{self, FromSql};
+use diesel::mysql::Mysql;
+use diesel::pg::{Pg, PgValue};
+use diesel::serialize::{self, IsNull, Output, ToSql};
+use diesel::sql_types::{Binary, Text, Uuid};
+use sid_encode::DecodeError;
+
+use crate::{decode_labeled, Label, Sid};
+
+// `ToSql` requires `Debug`, which prints the label, so every impl is bounded on `T: Label` to keep the pairs alike.
+
+/// Postgres `uuid` columns are the 16 bytes on the wire.
+impl<T: Label> ToSql<Uuid, Pg> for Sid<T> {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        out.write_all(&self.data)?;
+        Ok(IsNull::No)
+    }
+}
+
+impl<T: Label> FromSql<Uuid, Pg> for Sid<T> {
+    fn from_sql(value: PgValue<'_>) -> deserialize::Result<Self> {
+        let data = <[u8; 16]>::try_from(value.as_bytes()).map_err(|_| DecodeError::InvalidLength)?;
+        Ok(Sid::from(data))
+    }
+}
+
+impl<T: Label, DB> ToSql<Binary, DB> for Sid<T>
+where
+    DB: Backend,
+    [u8]: ToSql<Binary, DB>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+        self.data.as_slice().to_sql(out)
+    }
+}
+
+impl<T: Label, DB> FromSql<Binary, DB> for Sid<T>
+where
+    DB: Backend,
+    Vec<u8>: FromSql<Binary, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+        let bytes = Vec::<u8>::from_sql(bytes)?;
+        let data = <[u8; 16]>::try_from(bytes).map_err(|_| DecodeError::InvalidLength)?;
+        Ok(Sid::from(data))
+    }
+}
+
+/// Text columns hold the bare 27 char form. Reading also accepts the labeled form, if the label matches `T`.
+impl<T: Label> ToSql<Text, Pg> for Sid<T> {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        write!(out, "{}", self)?;
+        Ok(IsNull::No)
+    }
+}
+
+impl<T: Label> ToSql<Text, Mysql> for Sid<T> {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Mysql>) -> serialize::Result {
+        write!(out, "{}", self)?;
+        Ok(IsNull::No)
+    }
+}
+
+#[cfg(feature = "diesel-sqlite")]
+impl<T: Label> ToSql<Text, diesel::sqlite::Sqlite> for Sid<T> {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, diesel::sqlite::Sqlite>) -> serialize::Result {
+        out.set_value(self.to_string());
+        Ok(IsNull::No)
+    }
+}
+
+impl<T: Label, DB> FromSql<Text, DB> for Sid<T>
+where
+    DB: Backend,
+    String: FromSql<Text, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+        let text = String::from_sql(bytes)?;
+        Ok(Sid::from(decode_labeled::<T>(&text)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use crate::{label, Label, Sid};
+    use diesel::deserialize::FromSql;
+    use diesel::mysql::{Mysql, MysqlType, MysqlValue};
+    use diesel::pg::{Pg, PgMetadataLookup, PgTypeMetadata, PgValue};
+    use diesel::query_builder::bind_collector::RawBytesBindCollector;
+    use diesel::query_builder::BindCollector;
+    use diesel::sql_types::{Binary, HasSqlType, Text, Uuid};
+    use diesel::serialize::ToSql;
+
+    label!(User, "usr");
+    label!(Team, "team");
+
+    // uuid, bytea and text have fixed oids, so nothing gets looked up.
+    struct NoLookup;
+
+    impl PgMetadataLookup for NoLookup {
+        fn lookup_type(&mut self, type_name: &str, _: Option<&str>) -> PgTypeMetadata {
+            unreachable!("looked up {}", type_name)
+        }
+    }
+
+    fn pg_bytes<ST>(sid: &Sid<User>) -> Vec<u8>
+    where
+        Pg: HasSqlType<ST>,
+        Sid<User>: ToSql<ST, Pg>,
+    {
+        let mut collector = RawBytesBindCollector::<Pg>::new();
+        collector.push_bound_value::<ST, _>(sid, &mut NoLookup).unwrap();
+        collector.binds.remove(0).unwrap()
+    }
+
+    fn pg_value<ST, T: Label>(bytes: &[u8]) -> diesel::deserialize::Result<Sid<T>>
+    where
+        Sid<T>: FromSql<ST, Pg>,
+    {
+        let oid = || NonZeroU32::new(1).unwrap();
+        <Sid<T> as FromSql<ST, Pg>>::from_sql(PgValue::new(bytes, &oid))
+    }
+
+    fn mysql_bytes<ST>(sid: &Sid<User>) -> Vec<u8>
+    where
+        Mysql: HasSqlType<ST>,
+        Sid<User>: ToSql<ST, Mysql>,
+    {
+        let mut collector = RawBytesBindCollector::<Mysql>::new();
+        collector.push_bound_value::<ST, _>(sid, &mut ()).unwrap();
+        collector.binds.remove(0).unwrap()
+    }
+
+    fn mysql_value<ST, T: Label>(bytes: &[u8], tpe: MysqlType) -> diesel::deserialize::Result<Sid<T>>
+    where
+        Sid<T>: FromSql<ST, Mysql>,
+    {
+        <Sid<T> as FromSql<ST, Mysql>>::from_sql(MysqlValue::new(bytes, tpe))
+    }
+
+    #[test]
+    fn test_pg() {
+        let sid = Sid::<User>::new();
+        let bytes = pg_bytes::<Uuid>(&sid);
+        assert_eq!(&bytes, sid.data());
+        assert_eq!(pg_value::<Uuid, User>(&bytes).unwrap(), sid);
+        assert!(pg_value::<Uuid, User>(&bytes[..15]).is_err());
+
+        let bytes = pg_bytes::<Binary>(&sid);
+        assert_eq!(&bytes, sid.data());
+        assert_eq!(pg_value::<Binary, User>(&bytes).unwrap(), sid);
+
+        let bytes = pg_bytes::<Text>(&sid);
+        assert_eq!(bytes, sid.to_string().into_bytes());
+        assert_eq!(pg_value::<Text, User>(&bytes).unwrap(), sid);
+        let labeled = format!("{:?}", sid);
+        assert_eq!(pg_value::<Text, User>(labeled.as_bytes()).unwrap(), sid);
+        assert!(pg_value::<Text, Team>(labeled.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_mysql() {
+        let sid = Sid::<User>::new();
+        let bytes = mysql_bytes::<Binary>(&sid);
+        assert_eq!(&bytes, sid.data());
+        assert_eq!(mysql_value::<Binary, User>(&bytes, MysqlType::Blob).unwrap(), sid);
+        assert!(mysql_value::<Binary, User>(&bytes[..15], MysqlType::Blob).is_err());
+
+        let bytes = mysql_bytes::<Text>(&sid);
+        assert_eq!(bytes, sid.to_string().into_bytes());
+        assert_eq!(mysql_value::<Text, User>(&bytes, MysqlType::String).unwrap(), sid);
+        let labeled = format!("{:?}", sid);
+        assert_eq!(mysql_value::<Text, User>(labeled.as_bytes(), MysqlType::String).unwrap(), sid);
+        assert!(mysql_value::<Text, Team>(labeled.as_bytes(), MysqlType::String).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "diesel-sqlite")]
+    fn test_sqlite() {
+        use diesel::prelude::*;
+        use diesel::sqlite::SqliteConnection;
+
+        #[derive(QueryableByName)]
+        struct Row {
+            #[diesel(sql_type = Binary)]
+            id: Sid<User>,
+            #[diesel(sql_type = Text)]
+            text: Sid<User>,
+        }
+
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        let sid = Sid::<User>::new();
+        let row: Row = diesel::sql_query("SELECT ? AS id, ? AS text")
+            .bind::<Binary, _>(sid)
+            .bind::<Text, _>(sid)
+            .get_result(&mut conn)
+            .unwrap();
+        assert_eq!(row.id, sid);
+        assert_eq!(row.text, sid);
+
+        let text: Sid<User> = diesel::select(format!("{:?}", sid).into_sql::<Text>())
+            .get_result(&mut conn)
+            .unwrap();
+        assert_eq!(text, sid);
+        let wrong = diesel::select(format!("{:?}", Sid::<Team>::new()).into_sql::<Text>())
+            .get_result::<Sid<User>>(&mut conn);
+        assert!(wrong.is_err());
+    }
+}
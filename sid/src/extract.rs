@@ -0,0 +1,127 @@
+//! The framework-independent half of the axum and actix-web extractors.
+
+/// Module docs for a framework's extractors.
+macro_rules! extractor_docs {
+    ($framework:literal) => {
+        concat!(
+            $framework,
+            " extractors. `Path` and `Query` are drop-in replacements for ",
+            $framework,
+            "'s, but reject a malformed sid, or one with the wrong label, with a 400 and a JSON body carrying ",
+            "the decode error, e.g.\n",
+            "`{\"error\": \"Invalid label while decoding sid: expected \\\"usr\\\", found \\\"team\\\"\"}`.\n",
+            "As with serde, sids may be given in the bare or the labeled form.",
+        )
+    };
+}
+
+/// Defines `Path` and `Query` newtypes over the framework's own extractors. The `FromRequest` impls stay in the
+/// framework's module.
+macro_rules! extractors {
+    ($path:literal, $query:literal) => {
+        #[doc = concat!("Like `", $path, "`, but with a JSON rejection.")]
+        #[derive(Debug, Clone, Copy, Default)]
+        pub struct Path<T>(pub T);
+
+        #[doc = concat!("Like `", $query, "`, but with a JSON rejection.")]
+        #[derive(Debug, Clone, Copy, Default)]
+        pub struct Query<T>(pub T);
+
+        impl<T> std::ops::Deref for Path<T> {
+            type Target = T;
+
+            fn deref(&self) -> &T {
+                &self.0
+            }
+        }
+
+        impl<T> std::ops::DerefMut for Path<T> {
+            fn deref_mut(&mut self) -> &mut T {
+                &mut self.0
+            }
+        }
+
+        impl<T> std::ops::Deref for Query<T> {
+            type Target = T;
+
+            fn deref(&self) -> &T {
+                &self.0
+            }
+        }
+
+        impl<T> std::ops::DerefMut for Query<T> {
+            fn deref_mut(&mut self) -> &mut T {
+                &mut self.0
+            }
+        }
+    };
+}
+
+pub(crate) use {extractor_docs, extractors};
+
+/// The JSON body of a rejection.
+pub(crate) fn error_body(message: &str) -> serde_json::Value {
+    serde_json::json!({ "error": message })
+}
+
+/// Both frameworks' tests run these against an app routing `/users/{id}` to `Path<Sid<User>>` and `/users` to
+/// `Query<Params>`, each responding with the sid's `to_string`, so the two give the same responses.
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::future::Future;
+
+    use serde::Deserialize;
+
+    use crate::{label, Label, Sid};
+
+    label!(User, "usr");
+
+    #[derive(Deserialize)]
+    pub struct Params {
+        pub id: Sid<User>,
+    }
+
+    const WRONG_LABEL: &str = "Invalid label while decoding sid: expected \"usr\", found \"team\"";
+
+    async fn assert_rejected<F, Fut>(get_uri: &F, uri: String, message: Option<&str>)
+    where
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = (u16, String)>,
+    {
+        let (status, body) = get_uri(uri).await;
+        assert_eq!(status, 400, "{}", body);
+        let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+        match message {
+            Some(message) => assert_eq!(body["error"], message),
+            None => assert!(body["error"].is_string(), "{}", body),
+        }
+    }
+
+    pub async fn check_path<F, Fut>(get_uri: F)
+    where
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = (u16, String)>,
+    {
+        let sid = Sid::<User>::new();
+        assert_eq!(get_uri(format!("/users/{:?}", sid)).await, (200, sid.to_string()));
+        if !cfg!(feature = "strict_labels") {
+            assert_eq!(get_uri(format!("/users/{}", sid)).await, (200, sid.to_string()));
+        }
+        assert_rejected(&get_uri, format!("/users/team_{}", sid), Some(WRONG_LABEL)).await;
+        assert_rejected(&get_uri, "/users/nope".to_string(), None).await;
+    }
+
+    pub async fn check_query<F, Fut>(get_uri: F)
+    where
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = (u16, String)>,
+    {
+        let sid = Sid::<User>::new();
+        assert_eq!(get_uri(format!("/users?id={:?}", sid)).await, (200, sid.to_string()));
+        if !cfg!(feature = "strict_labels") {
+            assert_eq!(get_uri(format!("/users?id={}", sid)).await, (200, sid.to_string()));
+        }
+        assert_rejected(&get_uri, format!("/users?id=team_{}", sid), Some(WRONG_LABEL)).await;
+        assert_rejected(&get_uri, "/users?id=nope".to_string(), None).await;
+    }
+}
@@ -0,0 +1,81 @@
+use juniper::{graphql_scalar, InputValue, ScalarValue, Value};
+
+use crate::{decode_labeled, Label, Sid};
+
+// juniper needs a static type name, so all labels share the `Sid` scalar.
+#[graphql_scalar(
+    name = "Sid",
+    description = "A sid in its labeled form, e.g. usr_0da0fa0e02cssbhkanf04c_srb0. Input may also omit the label, \
+        but a label that doesn't match the type is rejected.",
+    with = scalar,
+    parse_token(String),
+    where(T: Label + 'static),
+)]
+type SidScalar<T> = Sid<T>;
+
+mod scalar {
+    use super::*;
+
+    pub(super) fn to_output<S: ScalarValue, T: Label>(sid: &SidScalar<T>) -> Value<S> {
+        Value::scalar(format!("{:?}", sid))
+    }
+
+    pub(super) fn from_input<S: ScalarValue, T: Label>(v: &InputValue<S>) -> Result<SidScalar<T>, String> {
+        let s = v.as_string_value().ok_or_else(|| format!("Expected `String`, found: {}", v))?;
+        decode_labeled::<T>(s).map(Sid::from).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{label, Label, Sid};
+    use juniper::{
+        graphql_object, DefaultScalarValue, EmptyMutation, EmptySubscription, FromInputValue, InputValue, RootNode,
+        ToInputValue, Variables,
+    };
+
+    label!(User, "usr");
+    label!(Team, "team");
+
+    struct Query;
+
+    #[graphql_object]
+    impl Query {
+        fn user(id: Sid<User>) -> Sid<User> {
+            id
+        }
+    }
+
+    #[test]
+    fn test_scalar() {
+        let sid = Sid::<User>::new();
+        let input: InputValue<DefaultScalarValue> = sid.to_input_value();
+        assert_eq!(input.as_string_value(), Some(format!("{:?}", sid).as_str()));
+        let bare = InputValue::<DefaultScalarValue>::scalar(sid.to_string());
+        assert_eq!(Sid::<User>::from_input_value(&bare), Ok(sid));
+        assert_eq!(
+            Sid::<Team>::from_input_value(&input).unwrap_err().message(),
+            "Invalid label while decoding sid: expected \"team\", found \"usr\""
+        );
+
+        let schema = RootNode::new(Query, EmptyMutation::<()>::new(), EmptySubscription::<()>::new());
+        let query = format!("{{ user(id: \"{}\") }}", sid);
+        let (value, errors) = juniper::execute_sync(&query, None, &schema, &Variables::new(), &()).unwrap();
+        assert!(errors.is_empty());
+        assert_eq!(
+            value.as_object_value().unwrap().get_field_value("user").unwrap().as_string_value(),
+            Some(format!("{:?}", sid).as_str())
+        );
+
+        let query = "query($id: Sid!) { user(id: $id) }";
+        let variables: Variables = [("id".to_string(), InputValue::scalar(format!("team_{}", sid)))]
+            .into_iter()
+            .collect();
+        let error = juniper::execute_sync(query, None, &schema, &variables, &()).unwrap_err();
+        assert!(
+            error.to_string().contains("Invalid label while decoding sid: expected \"usr\", found \"team\""),
+            "{}",
+            error
+        );
+    }
+}
@@ -1,19 +1,73 @@
 use std::cmp::Ordering;
 use std::fmt::{Debug, Display, Formatter};
 use std::str::FromStr;
+use std::time::Duration;
 
 pub use label::Label;
 pub use sid_encode::DecodeError;
 use sid_encode::{base32_decode, base32_encode, SHORT_LENGTH};
 
+#[cfg(feature = "actix-web")]
+pub mod actix_web;
+#[cfg(feature = "async-graphql")]
+mod async_graphql;
+#[cfg(feature = "axum")]
+pub mod axum;
+#[cfg(feature = "bincode")]
+mod bincode;
+#[cfg(feature = "borsh")]
+mod borsh;
+#[cfg(feature = "bson")]
+mod bson;
+#[cfg(feature = "bytemuck")]
+mod bytemuck;
+#[cfg(feature = "cipher")]
+mod cipher;
+#[cfg(feature = "clap")]
+pub mod clap;
+// Not `mod diesel`: the diesel derives on `Sid` expand to `use diesel;`, which would pick up a local module.
+#[cfg(feature = "diesel")]
+mod diesel_impls;
+#[cfg(any(feature = "axum", feature = "actix-web"))]
+mod extract;
+#[cfg(feature = "juniper")]
+mod juniper;
 mod label;
 mod monotonic;
+#[cfg(feature = "postgres-types")]
+mod postgres_types;
+#[cfg(feature = "prost")]
+mod prost;
+#[cfg(feature = "redis")]
+pub mod redis;
+#[cfg(feature = "rkyv")]
+mod rkyv;
+#[cfg(feature = "rusqlite")]
+pub mod rusqlite;
+#[cfg(feature = "schemars")]
+mod schemars;
+#[cfg(feature = "sea-orm")]
+mod sea_orm;
 #[cfg(feature = "serde")]
-mod serde;
-#[cfg(feature = "sqlx")]
+pub mod serde;
+#[cfg(feature = "signed")]
+mod signed;
+#[cfg(any(feature = "sqlx-postgres", feature = "sqlx-sqlite", feature = "sqlx-mysql"))]
 mod sqlx;
-
-pub use monotonic::MonotonicGenerator;
+#[cfg(feature = "tonic")]
+pub mod tonic;
+#[cfg(feature = "utoipa")]
+mod utoipa;
+
+#[cfg(feature = "cipher")]
+pub use cipher::{ObfuscatedSid, SidCipher};
+pub use monotonic::{MonotonicGenerator, SidIter};
+#[cfg(feature = "prost")]
+pub use self::prost::SidProto;
+#[cfg(feature = "signed")]
+pub use signed::{InvalidSignature, SidSigner, SignedSid};
+#[cfg(feature = "sqlx-pg-ext")]
+pub use self::sqlx::PgSid;
 
 #[cfg(target_arch = "wasm32")]
 fn unix_epoch_millis() -> u64 {
@@ -38,6 +92,27 @@ impl NoLabel {
     }
 }
 
+// repr(transparent) guarantees a Sid is laid out exactly like its 16 bytes, so slices of sids can be cast to and
+// from byte slices.
+#[repr(transparent)]
+#[cfg_attr(feature = "rkyv", derive(::rkyv::Archive, ::rkyv::Serialize, ::rkyv::Deserialize))]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(
+        zerocopy::FromBytes,
+        zerocopy::IntoBytes,
+        zerocopy::KnownLayout,
+        zerocopy::Immutable,
+        zerocopy::Unaligned
+    )
+)]
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow)
+)]
+#[cfg_attr(feature = "diesel", diesel(sql_type = diesel::sql_types::Uuid))]
+#[cfg_attr(feature = "diesel", diesel(sql_type = diesel::sql_types::Binary))]
+#[cfg_attr(feature = "diesel", diesel(sql_type = diesel::sql_types::Text))]
 pub struct Sid<T = NoLabel> {
     data: [u8; 16],
     marker: std::marker::PhantomData<T>,
@@ -71,10 +146,7 @@ impl<T> Ord for Sid<T> {
 
 impl<T> Clone for Sid<T> {
     fn clone(&self) -> Self {
-        Self {
-            data: self.data,
-            marker: Default::default(),
-        }
+        *self
     }
 }
 
@@ -85,10 +157,34 @@ pub fn sid<T: Label>() -> Sid<T> {
 }
 
 impl<T> Sid<T> {
+    /// The largest possible sid, e.g. for an inclusive upper bound on a range.
+    pub const MAX: Self = Self {
+        data: [0xff; 16],
+        marker: std::marker::PhantomData,
+    };
+
     #[cfg(feature = "uuid")]
     pub fn uuid(&self) -> uuid::Uuid {
         uuid::Uuid::from_bytes(self.data)
     }
+
+    pub fn as_u128(&self) -> u128 {
+        u128::from_be_bytes(self.data)
+    }
+
+    /// The smallest sid greater than this one, or None if this is `Sid::MAX`.
+    pub fn checked_next(&self) -> Option<Self> {
+        self.as_u128().checked_add(1).map(Self::from)
+    }
+
+    /// The largest sid less than this one, or None if this is the null sid.
+    pub fn checked_prev(&self) -> Option<Self> {
+        self.as_u128().checked_sub(1).map(Self::from)
+    }
+
+    pub fn saturating_add(&self, n: u128) -> Self {
+        Self::from(self.as_u128().saturating_add(n))
+    }
 }
 
 impl<T: Label> Sid<T> {
@@ -99,10 +195,16 @@ impl<T: Label> Sid<T> {
         }
     }
 
+    #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
         Self::from_timestamp_with_rng(unix_epoch_millis(), &mut rand::rng())
     }
 
+    /// Generate `n` sorted, unique sids, sharing one rng across the batch.
+    pub fn batch(n: usize) -> Vec<Self> {
+        SidIter::new().take(n).collect()
+    }
+
     #[cfg(feature = "rand")]
     pub fn from_timestamp_with_rng<R>(timestamp: u64, rng: &mut R) -> Self
     where
@@ -135,6 +237,35 @@ impl<T: Label> Sid<T> {
         format!("{}{}{}", label, separator, &encoded[SHORT_LENGTH + 1..])
     }
 
+    /// A regex for the string form of `Sid<T>`, matching exactly what deserialization accepts,
+    /// e.g. ^(usr_)?[0-7][0-9a-hjkmnp-tv-z]{21}_[0-9a-hjkmnp-tv-z]{4}$. With `deserialize_uuid_strings`,
+    /// hyphenated uuids are an alternative.
+    pub fn pattern() -> String {
+        const CHAR: &str = "[0-9a-hjkmnp-tv-z]";
+        let label = T::label();
+        let mut escaped = String::with_capacity(label.len());
+        for c in label.chars() {
+            if "\\.+*?()|[]{}^$".contains(c) {
+                escaped.push('\\');
+            }
+            escaped.push(c);
+        }
+        let prefix = if label.is_empty() {
+            String::new()
+        } else if cfg!(feature = "strict_labels") {
+            format!("{}_", escaped)
+        } else {
+            format!("({}_)?", escaped)
+        };
+        let sid = format!("{}[0-7]{}{{21}}_{}{{4}}", prefix, CHAR, CHAR);
+        if cfg!(feature = "deserialize_uuid_strings") {
+            const HEX: &str = "[0-9a-fA-F]";
+            format!("^({sid}|{HEX}{{8}}-{HEX}{{4}}-{HEX}{{4}}-{HEX}{{4}}-{HEX}{{12}})$")
+        } else {
+            format!("^{}$", sid)
+        }
+    }
+
     pub fn is_null(&self) -> bool {
         self.data.iter().all(|&b| b == 0)
     }
@@ -143,30 +274,20 @@ impl<T: Label> Sid<T> {
         &self.data
     }
 
+    /// Milliseconds since the unix epoch, taken from the first 48 bits.
     pub fn timestamp(&self) -> u64 {
-        u64::from_be_bytes(self.data[0..8].try_into().unwrap())
+        u64::from_be_bytes(self.data[0..8].try_into().unwrap()) >> 16
+    }
+
+    /// The absolute difference between the creation times of two sids.
+    pub fn time_between(a: &Self, b: &Self) -> Duration {
+        Duration::from_millis(a.timestamp().abs_diff(b.timestamp()))
     }
 
     // small difference compared to ULID. Rather than erroring if we overflow the random buffer
     // we just increment the ms stamp.
     pub(crate) fn increment(&self) -> Self {
-        let mut data = self.data;
-        let mut i = 15;
-        loop {
-            let (value, overflow) = data[i].overflowing_add(1);
-            data[i] = value;
-            if !overflow {
-                break;
-            }
-            if i == 0 {
-                panic!("sid overflow");
-            }
-            i -= 1;
-        }
-        Self {
-            data,
-            marker: Default::default(),
-        }
+        self.checked_next().expect("sid overflow")
     }
 
     pub fn into_bytes(self) -> [u8; 16] {
@@ -175,9 +296,9 @@ impl<T: Label> Sid<T> {
 }
 
 #[cfg(feature = "uuid")]
-impl<T> Into<uuid::Uuid> for Sid<T> {
-    fn into(self) -> uuid::Uuid {
-        uuid::Uuid::from_bytes(self.data)
+impl<T> From<Sid<T>> for uuid::Uuid {
+    fn from(value: Sid<T>) -> Self {
+        uuid::Uuid::from_bytes(value.data)
     }
 }
 
@@ -221,12 +342,121 @@ impl<T> From<[u8; 16]> for Sid<T> {
     }
 }
 
-impl<T> FromStr for Sid<T> {
+impl<T> TryFrom<&[u8]> for Sid<T> {
+    type Error = DecodeError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        <[u8; 16]>::try_from(bytes)
+            .map(Self::from)
+            .map_err(|_| DecodeError::InvalidLength)
+    }
+}
+
+impl<T> From<u128> for Sid<T> {
+    fn from(value: u128) -> Self {
+        Self::from(value.to_be_bytes())
+    }
+}
+
+impl<T> From<Sid<T>> for u128 {
+    fn from(value: Sid<T>) -> Self {
+        value.as_u128()
+    }
+}
+
+/// Parses the bare form or the labeled form, e.g. usr_0da0fa0e02cssbhkanf04c_srb0, checking the label.
+impl<T: Label> FromStr for Sid<T> {
     type Err = DecodeError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let data = base32_decode(s)?;
-        Ok(Sid::<T>::from(data))
+        decode_labeled::<T>(s).map(Sid::from)
+    }
+}
+
+/// Decode either the bare form or the labeled form, e.g. usr_0da0fa0e02cssbhkanf04c_srb0,
+/// checking that the label matches `T`.
+pub(crate) fn decode_labeled<T: Label>(s: &str) -> Result<[u8; 16], DecodeError> {
+    let Some(split) = s.len().checked_sub(27) else {
+        return Err(DecodeError::InvalidLength);
+    };
+    if split == 0 {
+        return base32_decode(s);
+    }
+    if !s.is_char_boundary(split) {
+        return Err(DecodeError::InvalidLength);
+    }
+    let (prefix, encoded) = s.split_at(split);
+    let label = prefix.strip_suffix('_').ok_or(DecodeError::NoSeparator)?;
+    if label != T::label() {
+        return Err(DecodeError::InvalidLabel {
+            expected: T::label(),
+            found: label.to_string(),
+        });
+    }
+    base32_decode(encoded)
+}
+
+/// The postgres extension truncates labels to 8 bytes.
+#[cfg(any(feature = "postgres-types", feature = "sqlx-pg-ext"))]
+pub(crate) fn pg_ext_label<T: Label>() -> &'static [u8] {
+    let label = T::label().as_bytes();
+    &label[..label.len().min(8)]
+}
+
+/// A label stored by the postgres extension has to match `T`, but an unlabeled sid decodes as any `Sid<T>`.
+#[cfg(any(feature = "postgres-types", feature = "sqlx-pg-ext"))]
+pub(crate) fn check_pg_ext_label<T: Label>(label: &[u8]) -> Result<(), DecodeError> {
+    if label.is_empty() || label == pg_ext_label::<T>() {
+        return Ok(());
+    }
+    Err(DecodeError::InvalidLabel {
+        expected: T::label(),
+        found: String::from_utf8_lossy(label).into_owned(),
+    })
+}
+
+/// The binary form of the extension's `sid` type (`sid_send`/`sid_recv`) is the 16 data bytes followed by the
+/// label, if any. A `uuid` is just the 16 bytes, so this decodes those too.
+#[cfg(any(feature = "postgres-types", feature = "sqlx-pg-ext"))]
+pub(crate) fn decode_pg_ext<T: Label>(bytes: &[u8]) -> Result<[u8; 16], DecodeError> {
+    if bytes.len() < 16 || bytes.len() > 16 + 8 {
+        return Err(DecodeError::InvalidLength);
+    }
+    let (data, label) = bytes.split_at(16);
+    check_pg_ext_label::<T>(label)?;
+    Ok(data.try_into().unwrap())
+}
+
+#[cfg(any(feature = "async-graphql", feature = "schemars", feature = "utoipa"))]
+pub(crate) fn schema_name<T: Label>() -> std::borrow::Cow<'static, str> {
+    let label = T::label();
+    if label.is_empty() {
+        "Sid".into()
+    } else {
+        format!("Sid_{}", label).into()
+    }
+}
+
+#[cfg(any(feature = "schemars", feature = "utoipa"))]
+pub(crate) fn schema_description<T: Label>() -> String {
+    let label = T::label();
+    if label.is_empty() {
+        "A sid: 27 chars of crockford base32, e.g. 0da0fa0e02cssbhkanf04c_srb0".to_string()
+    } else if cfg!(feature = "strict_labels") {
+        format!("A {label} sid: 27 chars of crockford base32, prefixed with `{label}_`.")
+    } else {
+        format!("A {label} sid: 27 chars of crockford base32, optionally prefixed with `{label}_`.")
+    }
+}
+
+/// The null sid, in the form it serializes to.
+#[cfg(any(feature = "schemars", feature = "utoipa"))]
+pub(crate) fn schema_example<T: Label>() -> String {
+    let null = Sid::<T>::null();
+    if cfg!(feature = "serialize_labeled") {
+        format!("{:?}", null)
+    } else {
+        null.to_string()
     }
 }
 
@@ -324,12 +554,24 @@ mod tests {
         assert_eq!(sid.to_string(), "0000000000000000000000_0000");
     }
 
+    #[test]
+    fn test_from_str() {
+        let sid = Sid::<Team>::new();
+        assert_eq!(format!("{:?}", sid).parse::<Sid<Team>>().unwrap(), sid);
+        assert_eq!(sid.to_string().parse::<Sid<Team>>().unwrap(), sid);
+        assert!(matches!(
+            format!("usr_{}", sid).parse::<Sid<Team>>(),
+            Err(DecodeError::InvalidLabel { expected: "team", .. })
+        ));
+        assert_eq!(Sid::<NoLabel>::from_str(&sid.to_string()).unwrap(), sid.unlabel());
+    }
+
     #[test]
     #[cfg(feature = "uuid")]
     fn test_uuid() {
         let bytes = [1u8, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16];
         let sid = Sid::<Team>::from(bytes);
-        let uuid: uuid::Uuid = sid.clone().into();
+        let uuid: uuid::Uuid = sid.into();
         assert_eq!(uuid.to_string(), "01020304-0506-0708-090a-0b0c0d0e0f10");
         let uuid2 = sid.uuid();
         assert_eq!(uuid, uuid2);
@@ -348,6 +590,63 @@ mod tests {
     #[test]
     fn test_size() {
         assert_eq!(std::mem::size_of::<Sid<Team>>(), 16);
+        assert_eq!(std::mem::align_of::<Sid<Team>>(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "zerocopy")]
+    fn test_zerocopy() {
+        use zerocopy::{FromBytes, IntoBytes};
+
+        let sids = [Sid::<Team>::new(), Sid::<Team>::new()];
+        let bytes = sids.as_bytes();
+        assert_eq!(bytes.len(), 32);
+        assert_eq!(&bytes[16..], sids[1].data());
+        let cast = <[Sid<Team>]>::ref_from_bytes(bytes).unwrap();
+        assert_eq!(cast, &sids);
+        assert!(<[Sid<Team>]>::ref_from_bytes(&bytes[1..]).is_err());
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let sid = Sid::<Team>::null();
+        assert_eq!(sid.checked_prev(), None);
+        assert_eq!(sid.checked_next().unwrap().as_u128(), 1);
+        assert_eq!(Sid::<Team>::MAX.checked_next(), None);
+        assert_eq!(Sid::<Team>::MAX.checked_prev().unwrap().as_u128(), u128::MAX - 1);
+        assert_eq!(sid.saturating_add(u128::MAX), Sid::MAX);
+        assert_eq!(Sid::<Team>::MAX.saturating_add(1), Sid::MAX);
+
+        let bytes = [1u8, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16];
+        let sid = Sid::<Team>::from(bytes);
+        assert_eq!(sid.as_u128(), 0x0102030405060708090a0b0c0d0e0f10);
+        assert_eq!(Sid::<Team>::from(sid.as_u128()), sid);
+        assert_eq!(u128::from(sid), sid.as_u128());
+    }
+
+    #[test]
+    fn test_timestamp() {
+        let rng = &mut rand::rng();
+        let a = Sid::<Team>::from_timestamp_with_rng(1_700_000_000_000, rng);
+        let b = Sid::<Team>::from_timestamp_with_rng(1_700_000_001_500, rng);
+        assert_eq!(a.timestamp(), 1_700_000_000_000);
+        assert_eq!(Sid::time_between(&a, &b), Duration::from_millis(1500));
+        assert_eq!(Sid::time_between(&b, &a), Duration::from_millis(1500));
+    }
+
+    #[test]
+    fn test_batch() {
+        let sids = Sid::<Team>::batch(10_000);
+        assert_eq!(sids.len(), 10_000);
+        assert!(sids.windows(2).all(|w| w[0] < w[1]));
+
+        let mut iter = SidIter::<Team>::new();
+        let first = iter.next().unwrap();
+        assert!(iter.by_ref().take(5000).all(|sid| sid > first));
+
+        std::thread::sleep(Duration::from_millis(20));
+        let later = iter.next().unwrap();
+        assert!(later.timestamp() >= first.timestamp() + 20);
     }
 
     #[test]
@@ -355,11 +654,11 @@ mod tests {
         let ts = unix_epoch_millis();
         let ts2 = ts + 1;
         let ts3 = ts + 2;
-        let rng = &mut rand::thread_rng();
+        let rng = &mut rand::rng();
         let sid1 = Sid::<NoLabel>::from_timestamp_with_rng(ts, rng);
         let sid2 = Sid::from_timestamp_with_rng(ts2, rng);
         let sid3 = Sid::from_timestamp_with_rng(ts3, rng);
-        let mut sids = vec![sid3.clone(), sid1.clone(), sid2.clone()];
+        let mut sids = vec![sid3, sid1, sid2];
         sids.sort();
         assert_eq!(sids, vec![sid1, sid2, sid3]);
     }
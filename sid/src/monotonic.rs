@@ -1,10 +1,18 @@
 use std::time::{Duration, SystemTime};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use crate::{Label, NoLabel, Sid};
 
 pub struct MonotonicGenerator<T = NoLabel> {
     previous: Sid<T>,
 }
 
+impl<T: Label> Default for MonotonicGenerator<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T: Label> MonotonicGenerator<T> {
     pub fn new() -> Self {
         Self {
@@ -13,8 +21,7 @@ impl<T: Label> MonotonicGenerator<T> {
     }
 
     pub fn generate(&mut self) -> Sid<T> {
-        let now = SystemTime::now();
-        self.generate_from_datetime(now)
+        self.generate_from_timestamp_with_source(crate::unix_epoch_millis(), &mut rand::rng())
     }
 
     pub fn generate_from_datetime(&mut self, datetime: SystemTime) -> Sid<T> {
@@ -29,19 +36,63 @@ impl<T: Label> MonotonicGenerator<T> {
         where
             R: rand::Rng,
     {
-        let last_ms = self.previous.timestamp();
-        // maybe time went backward, or it is the same ms.
-        // increment instead of generating a new random so that it is monotonic
         let ts = datetime
             .duration_since(SystemTime::UNIX_EPOCH)
             .unwrap_or(Duration::ZERO)
             .as_millis() as u64;
+        self.generate_from_timestamp_with_source(ts, source)
+    }
+
+    /// Like `generate_from_datetime_with_source`, with the time given as milliseconds since the unix epoch.
+    pub fn generate_from_timestamp_with_source<R>(&mut self, ts: u64, source: &mut R) -> Sid<T>
+        where
+            R: rand::Rng,
+    {
+        let last_ms = self.previous.timestamp();
+        // maybe time went backward, or it is the same ms.
+        // increment instead of generating a new random so that it is monotonic
         let next = if ts <= last_ms {
             self.previous.increment()
         } else {
             Sid::from_timestamp_with_rng(ts, source)
         };
-        self.previous = next.clone();
+        self.previous = next;
         next
     }
-}
\ No newline at end of file
+}
+
+/// An endless, strictly increasing stream of sids. Unlike calling `Sid::new()` in a loop, the rng is
+/// set up once, and ids within the same millisecond increment instead of drawing new random bits.
+pub struct SidIter<T = NoLabel> {
+    generator: MonotonicGenerator<T>,
+    rng: StdRng,
+}
+
+impl<T: Label> Default for SidIter<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Label> SidIter<T> {
+    pub fn new() -> Self {
+        Self {
+            generator: MonotonicGenerator::new(),
+            rng: StdRng::from_rng(&mut rand::rng()),
+        }
+    }
+}
+
+impl<T: Label> Iterator for SidIter<T> {
+    type Item = Sid<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // `unix_epoch_millis` rather than `SystemTime::now`, which panics on wasm32.
+        let now = crate::unix_epoch_millis();
+        Some(self.generator.generate_from_timestamp_with_source(now, &mut self.rng))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
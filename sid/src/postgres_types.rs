@@ -0,0 +1,77 @@
+use std::error::Error;
+
+use bytes::BytesMut;
+use postgres_types::{to_sql_checked, FromSql, IsNull, ToSql, Type};
+
+use crate::{decode_pg_ext, pg_ext_label, Label, Sid};
+
+/// The postgres extension's type. It has no fixed OID, so match it by name.
+fn is_pg_ext(ty: &Type) -> bool {
+    ty.name() == "sid"
+}
+
+impl<T: Label> ToSql for Sid<T> {
+    fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        out.extend_from_slice(&self.data);
+        if is_pg_ext(ty) {
+            out.extend_from_slice(pg_ext_label::<T>());
+        }
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        *ty == Type::UUID || is_pg_ext(ty)
+    }
+
+    to_sql_checked!();
+}
+
+impl<'a, T: Label> FromSql<'a> for Sid<T> {
+    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        Ok(Sid::from(decode_pg_ext::<T>(raw)?))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        *ty == Type::UUID || is_pg_ext(ty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{label, DecodeError};
+    use postgres_types::Kind;
+
+    label!(User, "usr");
+    label!(Team, "team");
+
+    fn sid_type() -> Type {
+        Type::new("sid".to_string(), 16384, Kind::Simple, "public".to_string())
+    }
+
+    #[test]
+    fn test_uuid() {
+        let sid = Sid::<User>::new();
+        let mut buf = BytesMut::new();
+        sid.to_sql_checked(&Type::UUID, &mut buf).unwrap();
+        assert_eq!(&buf[..], sid.data());
+        assert_eq!(Sid::<User>::from_sql(&Type::UUID, &buf).unwrap(), sid);
+        assert!(sid.to_sql_checked(&Type::TEXT, &mut buf).is_err());
+    }
+
+    #[test]
+    fn test_pg_ext() {
+        let sid = Sid::<User>::new();
+        let mut buf = BytesMut::new();
+        sid.to_sql_checked(&sid_type(), &mut buf).unwrap();
+        assert_eq!(&buf[16..], b"usr");
+        assert_eq!(Sid::<User>::from_sql(&sid_type(), &buf).unwrap(), sid);
+
+        let err = Sid::<Team>::from_sql(&sid_type(), &buf).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<DecodeError>(),
+            Some(DecodeError::InvalidLabel { .. })
+        ));
+        assert!(<Sid<Team> as FromSql>::accepts(&sid_type()));
+    }
+}
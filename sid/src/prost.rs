@@ -0,0 +1,65 @@
+use sid_encode::DecodeError;
+
+use crate::Sid;
+
+/// A sid as a protobuf message, compatible with `message Sid { bytes data = 1; }`. The 16 bytes sort the same way as
+/// the string form.
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct SidProto {
+    #[prost(bytes = "vec", tag = "1")]
+    pub data: Vec<u8>,
+}
+
+impl<T> From<Sid<T>> for SidProto {
+    fn from(sid: Sid<T>) -> Self {
+        Self {
+            data: sid.data.to_vec(),
+        }
+    }
+}
+
+impl<T> TryFrom<SidProto> for Sid<T> {
+    type Error = DecodeError;
+
+    fn try_from(proto: SidProto) -> Result<Self, Self::Error> {
+        Sid::try_from(proto.data.as_slice())
+    }
+}
+
+impl<T> TryFrom<&SidProto> for Sid<T> {
+    type Error = DecodeError;
+
+    fn try_from(proto: &SidProto) -> Result<Self, Self::Error> {
+        Sid::try_from(proto.data.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{label, Label};
+    use prost::Message;
+
+    label!(User, "usr");
+
+    #[test]
+    fn test_round_trip() {
+        let sid = Sid::<User>::new();
+        let proto = SidProto::from(sid);
+        let bytes = proto.encode_to_vec();
+        assert_eq!(bytes.len(), 2 + 16);
+
+        let decoded = SidProto::decode(bytes.as_slice()).unwrap();
+        assert_eq!(Sid::<User>::try_from(decoded).unwrap(), sid);
+    }
+
+    #[test]
+    fn test_length_check() {
+        let proto = SidProto { data: vec![0; 15] };
+        assert!(matches!(Sid::<User>::try_from(&proto), Err(DecodeError::InvalidLength)));
+        assert!(matches!(
+            Sid::<User>::try_from(SidProto::default()),
+            Err(DecodeError::InvalidLength)
+        ));
+    }
+}
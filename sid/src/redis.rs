@@ -0,0 +1,113 @@
+//! redis support. Sids are written as the 27 char string, so they work as keys and sorted set members. Wrap a sid
+//! in `Labeled` to write the labeled form instead, e.g. `usr_0da0fa0e02cssbhkanf04c_srb0`. Reading accepts either.
+
+use std::fmt::{Debug, Formatter};
+
+use redis::{ErrorKind, FromRedisValue, RedisError, RedisResult, RedisWrite, ToRedisArgs, Value};
+
+use crate::{decode_labeled, Label, NoLabel, Sid};
+
+impl<T: Label> ToRedisArgs for Sid<T> {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        out.write_arg_fmt(self)
+    }
+}
+
+impl<T: Label> FromRedisValue for Sid<T> {
+    fn from_redis_value(v: &Value) -> RedisResult<Self> {
+        let s = String::from_redis_value(v)?;
+        decode_labeled::<T>(&s)
+            .map(Sid::from)
+            .map_err(|e| RedisError::from((ErrorKind::TypeError, "Invalid sid", e.to_string())))
+    }
+}
+
+/// Writes the labeled form of a sid, e.g. `usr_0da0fa0e02cssbhkanf04c_srb0`.
+pub struct Labeled<T = NoLabel>(pub Sid<T>);
+
+impl<T> Clone for Labeled<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Labeled<T> {}
+
+impl<T> PartialEq for Labeled<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T> Eq for Labeled<T> {}
+
+impl<T: Label> Debug for Labeled<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+impl<T> From<Sid<T>> for Labeled<T> {
+    fn from(sid: Sid<T>) -> Self {
+        Self(sid)
+    }
+}
+
+impl<T> From<Labeled<T>> for Sid<T> {
+    fn from(labeled: Labeled<T>) -> Self {
+        labeled.0
+    }
+}
+
+impl<T: Label> ToRedisArgs for Labeled<T> {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        out.write_arg_fmt(format_args!("{:?}", self.0))
+    }
+}
+
+impl<T: Label> FromRedisValue for Labeled<T> {
+    fn from_redis_value(v: &Value) -> RedisResult<Self> {
+        Sid::from_redis_value(v).map(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::label;
+
+    label!(User, "usr");
+    label!(Team, "team");
+
+    #[test]
+    fn test_args() {
+        let sid = Sid::<User>::new();
+        assert_eq!(sid.to_redis_args(), vec![sid.to_string().into_bytes()]);
+        assert_eq!(
+            Labeled(sid).to_redis_args(),
+            vec![format!("{:?}", sid).into_bytes()]
+        );
+        let cmd = redis::cmd("GET").arg(sid).clone();
+        assert_eq!(cmd.args_iter().count(), 2);
+    }
+
+    #[test]
+    fn test_from_value() {
+        let sid = Sid::<User>::new();
+        let bare = Value::BulkString(sid.to_string().into_bytes());
+        let labeled = Value::BulkString(format!("{:?}", sid).into_bytes());
+        assert_eq!(Sid::<User>::from_redis_value(&bare).unwrap(), sid);
+        assert_eq!(Sid::<User>::from_redis_value(&labeled).unwrap(), sid);
+        assert_eq!(Labeled::<User>::from_redis_value(&labeled).unwrap(), Labeled(sid));
+
+        let err = Sid::<Team>::from_redis_value(&labeled).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::TypeError);
+        assert!(Sid::<User>::from_redis_value(&Value::Nil).is_err());
+    }
+}
@@ -0,0 +1,48 @@
+use std::fmt::{Debug, Formatter};
+
+use crate::{ArchivedSid, Label, Sid};
+
+impl<T> ArchivedSid<T> {
+    /// Copy the sid out of the archive, without going through `rkyv::deserialize`.
+    pub fn sid(&self) -> Sid<T> {
+        Sid::from(self.data)
+    }
+
+    pub fn data(&self) -> &[u8; 16] {
+        &self.data
+    }
+}
+
+impl<T> PartialEq<Sid<T>> for ArchivedSid<T> {
+    fn eq(&self, other: &Sid<T>) -> bool {
+        self.data == other.data
+    }
+}
+
+impl<T: Label> Debug for ArchivedSid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&self.sid(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rkyv::rancor::Error;
+
+    use crate::{label, ArchivedSid, Label, Sid};
+
+    label!(User, "usr");
+
+    #[test]
+    fn test_round_trip() {
+        let sids = vec![Sid::<User>::new(), Sid::<User>::new()];
+        let bytes = rkyv::to_bytes::<Error>(&sids).unwrap();
+        let archived = rkyv::access::<rkyv::Archived<Vec<Sid<User>>>, Error>(&bytes).unwrap();
+        assert_eq!(archived[0], sids[0]);
+        assert_eq!(archived[1].sid(), sids[1]);
+        let deserialized: Vec<Sid<User>> = rkyv::deserialize::<_, Error>(archived).unwrap();
+        assert_eq!(deserialized, sids);
+
+        assert_eq!(std::mem::size_of::<ArchivedSid<User>>(), 16);
+    }
+}
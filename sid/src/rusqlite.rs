@@ -0,0 +1,155 @@
+//! rusqlite support. Sids are stored as 16 byte blobs, which sort by creation time, since the timestamp is stored
+//! big-endian. Reading also accepts text columns holding the bare or labeled form.
+
+use rusqlite::functions::{Context, FunctionFlags};
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+use rusqlite::{Connection, Error};
+use sid_encode::{base32_decode, base32_encode};
+
+use crate::{decode_labeled, DecodeError, Label, NoLabel, Sid};
+
+impl<T> ToSql for Sid<T> {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::Borrowed(ValueRef::Blob(&self.data)))
+    }
+}
+
+impl<T: Label> FromSql for Sid<T> {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        match value {
+            ValueRef::Blob(bytes) => <[u8; 16]>::try_from(bytes)
+                .map(Sid::from)
+                .map_err(|_| FromSqlError::InvalidBlobSize {
+                    expected_size: 16,
+                    blob_size: bytes.len(),
+                }),
+            ValueRef::Text(_) => {
+                let text = value.as_str()?;
+                decode_labeled::<T>(text)
+                    .map(Sid::from)
+                    .map_err(|e| FromSqlError::Other(Box::new(e)))
+            }
+            _ => Err(FromSqlError::InvalidType),
+        }
+    }
+}
+
+/// Register SQL functions for reading sids in ad-hoc queries:
+///
+/// - `sid_to_text(id)` and `sid_to_text(id, label)`, e.g. `usr_0da0fa0e02cssbhkanf04c_srb0`
+/// - `sid_timestamp(id)`, the creation time in ms since the unix epoch
+///
+/// Both take a 16 byte blob, or text with any label, and return NULL for NULL.
+pub fn register_functions(conn: &Connection) -> rusqlite::Result<()> {
+    let flags = FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC;
+    conn.create_scalar_function("sid_to_text", 1, flags, |ctx| {
+        Ok(sid_arg(ctx)?.map(base32_encode))
+    })?;
+    conn.create_scalar_function("sid_to_text", 2, flags, |ctx| {
+        let Some(data) = sid_arg(ctx)? else {
+            return Ok(None);
+        };
+        let label = ctx.get::<Option<String>>(1)?.unwrap_or_default();
+        if label.is_empty() {
+            return Ok(Some(base32_encode(data)));
+        }
+        Ok(Some(format!("{}_{}", label, base32_encode(data))))
+    })?;
+    conn.create_scalar_function("sid_timestamp", 1, flags, |ctx| {
+        Ok(sid_arg(ctx)?.map(|data| Sid::<NoLabel>::from(data).timestamp() as i64))
+    })
+}
+
+fn sid_arg(ctx: &Context<'_>) -> rusqlite::Result<Option<[u8; 16]>> {
+    let data = match ctx.get_raw(0) {
+        ValueRef::Null => return Ok(None),
+        ValueRef::Blob(bytes) => <[u8; 16]>::try_from(bytes).map_err(|_| DecodeError::InvalidLength),
+        ValueRef::Text(_) => {
+            // the label, if any, is ignored
+            let text = ctx.get::<String>(0)?;
+            match text.len().checked_sub(27) {
+                Some(split) if text.is_char_boundary(split) => base32_decode(&text[split..]),
+                _ => Err(DecodeError::InvalidLength),
+            }
+        }
+        _ => Err(DecodeError::InvalidLength),
+    };
+    data.map(Some).map_err(|e| Error::UserFunctionError(Box::new(e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::label;
+
+    label!(User, "usr");
+    label!(Team, "team");
+
+    #[test]
+    fn test_round_trip() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute("CREATE TABLE users (id BLOB PRIMARY KEY, text TEXT)", ())
+            .unwrap();
+        let sid = Sid::<User>::new();
+        conn.execute("INSERT INTO users VALUES (?1, ?2)", (sid, sid.to_string()))
+            .unwrap();
+
+        let (id, text): (Sid<User>, Sid<User>) = conn
+            .query_row("SELECT id, text FROM users", (), |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap();
+        assert_eq!(id, sid);
+        assert_eq!(text, sid);
+
+        let labeled: Sid<User> = conn
+            .query_row("SELECT ?1", [format!("{:?}", sid)], |row| row.get(0))
+            .unwrap();
+        assert_eq!(labeled, sid);
+        let wrong = conn.query_row("SELECT ?1", [format!("{:?}", sid)], |row| {
+            row.get::<_, Sid<Team>>(0)
+        });
+        assert!(wrong.is_err());
+    }
+
+    #[test]
+    fn test_blobs_sort() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute("CREATE TABLE users (id BLOB PRIMARY KEY)", ()).unwrap();
+        let sids = Sid::<User>::batch(100);
+        for sid in sids.iter().rev() {
+            conn.execute("INSERT INTO users VALUES (?1)", [sid]).unwrap();
+        }
+        let mut stmt = conn.prepare("SELECT id FROM users ORDER BY id").unwrap();
+        let sorted = stmt
+            .query_map((), |row| row.get::<_, Sid<User>>(0))
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(sorted, sids);
+    }
+
+    #[test]
+    fn test_functions() {
+        let conn = Connection::open_in_memory().unwrap();
+        register_functions(&conn).unwrap();
+        let sid = Sid::<User>::new();
+        let (text, labeled, timestamp): (String, String, i64) = conn
+            .query_row(
+                "SELECT sid_to_text(?1), sid_to_text(?1, 'usr'), sid_timestamp(?1)",
+                [sid],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .unwrap();
+        assert_eq!(text, sid.to_string());
+        assert_eq!(labeled, format!("{:?}", sid));
+        assert_eq!(timestamp as u64, sid.timestamp());
+
+        let from_text: i64 = conn
+            .query_row("SELECT sid_timestamp(?1)", [labeled], |row| row.get(0))
+            .unwrap();
+        assert_eq!(from_text as u64, sid.timestamp());
+        let null: Option<String> = conn
+            .query_row("SELECT sid_to_text(NULL)", (), |row| row.get(0))
+            .unwrap();
+        assert_eq!(null, None);
+    }
+}
@@ -0,0 +1,69 @@
+use std::borrow::Cow;
+
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+
+use crate::{schema_description, schema_example, schema_name, Label, Sid};
+
+impl<T: Label> JsonSchema for Sid<T> {
+    fn schema_name() -> Cow<'static, str> {
+        schema_name::<T>()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        Cow::Owned(format!("sid::Sid<{}>", T::label()))
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "pattern": Sid::<T>::pattern(),
+            "description": schema_description::<T>(),
+            "examples": [schema_example::<T>()],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{label, Label, NoLabel, Sid};
+    use schemars::schema_for;
+
+    label!(User, "usr");
+
+    #[test]
+    fn test_schema() {
+        let schema = schema_for!(Sid<User>);
+        assert_eq!(schema.get("type").unwrap(), "string");
+        assert_eq!(schema.get("title").unwrap(), "Sid_usr");
+        let pattern = schema.get("pattern").unwrap().as_str().unwrap();
+        let sid = if cfg!(feature = "strict_labels") {
+            "usr_[0-7][0-9a-hjkmnp-tv-z]{21}_[0-9a-hjkmnp-tv-z]{4}"
+        } else {
+            "(usr_)?[0-7][0-9a-hjkmnp-tv-z]{21}_[0-9a-hjkmnp-tv-z]{4}"
+        };
+        assert_eq!(pattern, anchored(sid));
+        // strict_labels implies serialize_labeled, so the example always matches the pattern.
+        let example = if cfg!(feature = "serialize_labeled") {
+            "usr_0000000000000000000000_0000"
+        } else {
+            "0000000000000000000000_0000"
+        };
+        assert_eq!(schema.get("examples").unwrap()[0], example);
+
+        let schema = schema_for!(Sid<NoLabel>);
+        assert_eq!(
+            schema.get("pattern").unwrap(),
+            &anchored("[0-7][0-9a-hjkmnp-tv-z]{21}_[0-9a-hjkmnp-tv-z]{4}")
+        );
+        assert_eq!(schema.get("examples").unwrap()[0], "0000000000000000000000_0000");
+    }
+
+    fn anchored(sid: &str) -> String {
+        if cfg!(feature = "deserialize_uuid_strings") {
+            let uuid = "[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}";
+            format!("^({}|{})$", sid, uuid)
+        } else {
+            format!("^{}$", sid)
+        }
+    }
+}
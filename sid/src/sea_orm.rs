@@ -0,0 +1,92 @@
+use sea_orm::sea_query::{ArrayType, ColumnType, Nullable, Value, ValueType, ValueTypeErr};
+use sea_orm::{ColIdx, DbErr, QueryResult, TryFromU64, TryGetError, TryGetable};
+use uuid::Uuid;
+
+use crate::Sid;
+
+/// Stored as uuid, like the sqlx impl.
+impl<T> From<Sid<T>> for Value {
+    fn from(sid: Sid<T>) -> Self {
+        Value::Uuid(Some(Box::new(sid.uuid())))
+    }
+}
+
+impl<T> TryGetable for Sid<T> {
+    fn try_get_by<I: ColIdx>(res: &QueryResult, index: I) -> Result<Self, TryGetError> {
+        Uuid::try_get_by(res, index).map(Sid::from)
+    }
+}
+
+impl<T> ValueType for Sid<T> {
+    fn try_from(v: Value) -> Result<Self, ValueTypeErr> {
+        <Uuid as ValueType>::try_from(v).map(Sid::from)
+    }
+
+    fn type_name() -> String {
+        "Sid".to_string()
+    }
+
+    fn array_type() -> ArrayType {
+        ArrayType::Uuid
+    }
+
+    fn column_type() -> ColumnType {
+        ColumnType::Uuid
+    }
+}
+
+impl<T> Nullable for Sid<T> {
+    fn null() -> Value {
+        Value::Uuid(None)
+    }
+}
+
+/// Required for primary keys, but sids are never auto-incremented.
+impl<T> TryFromU64 for Sid<T> {
+    fn try_from_u64(_: u64) -> Result<Self, DbErr> {
+        Err(DbErr::ConvertFromU64("Sid"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{label, Label, Sid};
+    use sea_orm::entity::prelude::*;
+    use sea_orm::{DatabaseBackend, MockDatabase};
+
+    label!(User, "usr");
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+    #[sea_orm(table_name = "users")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: Sid<User>,
+        pub team_id: Option<Sid<User>>,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+
+    #[tokio::test]
+    async fn test_entity() {
+        let model = Model {
+            id: Sid::new(),
+            team_id: None,
+        };
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([[model.clone()]])
+            .into_connection();
+        let found = Entity::find_by_id(model.id).one(&db).await.unwrap();
+        assert_eq!(found, Some(model.clone()));
+
+        let log = db.into_transaction_log();
+        let values = log[0].statements()[0].values.as_ref().unwrap();
+        assert_eq!(values.0[0], Value::Uuid(Some(Box::new(model.id.uuid()))));
+        assert_eq!(
+            <Sid<User> as sea_orm::sea_query::ValueType>::column_type(),
+            ColumnType::Uuid
+        );
+    }
+}
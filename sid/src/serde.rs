@@ -1,48 +1,454 @@
-use crate::{NoLabel, Sid};
-use serde::de::{self, Visitor};
+//! `Serialize`/`Deserialize` for `Sid`, plus `#[serde(with = "...")]` helpers for other wire
+//! representations. Each helper module has `option` and `vec` submodules for `Option<Sid<T>>` and
+//! `Vec<Sid<T>>` fields.
+use crate::{decode_labeled, DecodeError, Label, Sid};
+use serde::de::{self, SeqAccess, Visitor};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Cow;
 use std::fmt;
+use std::marker::PhantomData;
 
-impl<T> Serialize for Sid<T> {
+impl<T: Label> Serialize for Sid<T> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        serializer.serialize_str(&self.to_string())
+        if !serializer.is_human_readable() {
+            serializer.serialize_bytes(&self.data)
+        } else if cfg!(feature = "serialize_labeled") {
+            serializer.collect_str(&format_args!("{:?}", self))
+        } else {
+            serializer.serialize_str(&self.to_string())
+        }
     }
 }
 
-impl<'de, T> Deserialize<'de> for Sid<T> {
+impl<'de, T: Label> Deserialize<'de> for Sid<T> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        struct SidVisitor;
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(SidVisitor::new(true))
+        } else {
+            deserializer.deserialize_bytes(SidVisitor::new(false))
+        }
+    }
+}
+
+struct SidVisitor<T> {
+    human_readable: bool,
+    label: PhantomData<T>,
+}
+
+impl<T> SidVisitor<T> {
+    fn new(human_readable: bool) -> Self {
+        Self { human_readable, label: PhantomData }
+    }
+}
+
+impl<'de, T: Label> Visitor<'de> for SidVisitor<T> {
+    type Value = Sid<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sid (27 chars, crockford base32, optionally labeled, or 16 bytes)")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        #[cfg(feature = "deserialize_uuid_strings")]
+        if value.len() == 36 {
+            return UuidVisitor::new().visit_str(value);
+        }
+        // sids written before the binary form existed are strings, which binary formats hand back
+        // as bytes or, if self-describing (e.g. MessagePack), as strings. Those were never labeled,
+        // so only apply strict_labels to human readable formats.
+        let label = T::label();
+        if cfg!(feature = "strict_labels") && self.human_readable && !label.is_empty() && value.len() == 27 {
+            return Err(E::custom(DecodeError::MissingLabel { expected: label }));
+        }
+        decode_labeled::<T>(value).map(Sid::from).map_err(E::custom)
+    }
+
+    fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        // legacy strings, see visit_str.
+        match <[u8; 16]>::try_from(value) {
+            Ok(data) => Ok(Sid::from(data)),
+            Err(_) => match std::str::from_utf8(value) {
+                Ok(value) => decode_labeled::<T>(value).map(Sid::from).map_err(E::custom),
+                Err(_) => Err(E::invalid_length(value.len(), &self)),
+            },
+        }
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut data = [0u8; 16];
+        for (i, byte) in data.iter_mut().enumerate() {
+            *byte = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+        Ok(Sid::from(data))
+    }
+}
+
+#[cfg(feature = "uuid")]
+struct UuidVisitor<T>(PhantomData<T>);
+
+#[cfg(feature = "uuid")]
+impl<T> UuidVisitor<T> {
+    fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl<'de, T: Label> Visitor<'de> for UuidVisitor<T> {
+    type Value = Sid<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a uuid string or 16 bytes")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        let uuid = uuid::Uuid::parse_str(value).map_err(E::custom)?;
+        Ok(Sid::from(uuid))
+    }
+
+    fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        SidVisitor::new(false).visit_bytes(value)
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        SidVisitor::new(false).visit_seq(seq)
+    }
+}
+
+trait Repr {
+    fn serialize<T: Label, S: Serializer>(sid: &Sid<T>, serializer: S) -> Result<S::Ok, S::Error>;
+    fn deserialize<'de, T: Label, D: Deserializer<'de>>(deserializer: D) -> Result<Sid<T>, D::Error>;
+}
+
+struct With<R, T>(Sid<T>, PhantomData<R>);
+
+impl<R, T> With<R, T> {
+    fn new(sid: Sid<T>) -> Self {
+        Self(sid, PhantomData)
+    }
+}
+
+impl<R: Repr, T: Label> Serialize for With<R, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        R::serialize(&self.0, serializer)
+    }
+}
+
+impl<'de, R: Repr, T: Label> Deserialize<'de> for With<R, T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        R::deserialize(deserializer).map(With::new)
+    }
+}
+
+#[cfg(feature = "uuid")]
+struct AsUuid;
+
+#[cfg(feature = "uuid")]
+impl Repr for AsUuid {
+    fn serialize<T: Label, S: Serializer>(sid: &Sid<T>, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(&sid.uuid().hyphenated())
+        } else {
+            serializer.serialize_bytes(&sid.data)
+        }
+    }
+
+    fn deserialize<'de, T: Label, D: Deserializer<'de>>(deserializer: D) -> Result<Sid<T>, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(UuidVisitor::new())
+        } else {
+            deserializer.deserialize_bytes(UuidVisitor::new())
+        }
+    }
+}
+
+struct Labeled;
+
+impl Repr for Labeled {
+    fn serialize<T: Label, S: Serializer>(sid: &Sid<T>, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&format_args!("{:?}", sid))
+    }
+
+    fn deserialize<'de, T: Label, D: Deserializer<'de>>(deserializer: D) -> Result<Sid<T>, D::Error> {
+        let value = <Cow<'de, str>>::deserialize(deserializer)?;
+        decode_labeled::<T>(&value)
+            .map(Sid::from)
+            .map_err(de::Error::custom)
+    }
+}
+
+struct AsBytes;
+
+impl Repr for AsBytes {
+    fn serialize<T: Label, S: Serializer>(sid: &Sid<T>, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&sid.data)
+    }
+
+    fn deserialize<'de, T: Label, D: Deserializer<'de>>(deserializer: D) -> Result<Sid<T>, D::Error> {
+        let human_readable = deserializer.is_human_readable();
+        deserializer.deserialize_bytes(SidVisitor::new(human_readable))
+    }
+}
+
+struct AsU128;
+
+impl Repr for AsU128 {
+    fn serialize<T: Label, S: Serializer>(sid: &Sid<T>, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u128(sid.as_u128())
+    }
+
+    fn deserialize<'de, T: Label, D: Deserializer<'de>>(deserializer: D) -> Result<Sid<T>, D::Error> {
+        u128::deserialize(deserializer).map(Sid::from)
+    }
+}
+
+#[cfg(feature = "bson")]
+struct AsBson;
+
+#[cfg(feature = "bson")]
+impl Repr for AsBson {
+    fn serialize<T: Label, S: Serializer>(sid: &Sid<T>, serializer: S) -> Result<S::Ok, S::Error> {
+        ::bson::Uuid::from(*sid).serialize(serializer)
+    }
+
+    fn deserialize<'de, T: Label, D: Deserializer<'de>>(deserializer: D) -> Result<Sid<T>, D::Error> {
+        let value = ::bson::Bson::deserialize(deserializer)?;
+        crate::bson::from_bson(&value).map_err(de::Error::custom)
+    }
+}
+
+macro_rules! with_module {
+    ($(#[$meta:meta])* $name:ident, $repr:ident) => {
+        $(#[$meta])*
+        pub mod $name {
+            use serde::{Deserializer, Serializer};
+            use crate::{Label, Sid};
+
+            pub fn serialize<T: Label, S: Serializer>(sid: &Sid<T>, serializer: S) -> Result<S::Ok, S::Error> {
+                <$crate::serde::$repr as $crate::serde::Repr>::serialize(sid, serializer)
+            }
 
-        impl<'de> Visitor<'de> for SidVisitor {
-            type Value = Sid<NoLabel>;
+            pub fn deserialize<'de, T: Label, D: Deserializer<'de>>(deserializer: D) -> Result<Sid<T>, D::Error> {
+                <$crate::serde::$repr as $crate::serde::Repr>::deserialize(deserializer)
+            }
+
+            /// For `Option<Sid<T>>` fields.
+            pub mod option {
+                use serde::{Deserialize, Deserializer, Serialize, Serializer};
+                use crate::{Label, Sid};
+                use $crate::serde::With;
 
-            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                formatter.write_str("a sid (27 chars, crockford base32)")
+                pub fn serialize<T: Label, S: Serializer>(sid: &Option<Sid<T>>, serializer: S) -> Result<S::Ok, S::Error> {
+                    sid.map(With::<$crate::serde::$repr, T>::new).serialize(serializer)
+                }
+
+                pub fn deserialize<'de, T: Label, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Sid<T>>, D::Error> {
+                    let value = Option::<With<$crate::serde::$repr, T>>::deserialize(deserializer)?;
+                    Ok(value.map(|w| w.0))
+                }
             }
 
-            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
-            where
-                E: de::Error,
-            {
-                let deserialize_uuid = cfg!(feature = "deserialize_uuid_strings");
-                let sid: Self::Value = if deserialize_uuid && value.len() == 36 {
-                    use std::str::FromStr;
-                    let uuid = uuid::Uuid::from_str(value).map_err(E::custom)?;
-                    Self::Value::from(uuid)
-                } else {
-                    value.parse().map_err(E::custom)?
-                };
-                Ok(sid)
+            /// For `Vec<Sid<T>>` fields.
+            pub mod vec {
+                use serde::{Deserialize, Deserializer, Serializer};
+                use crate::{Label, Sid};
+                use $crate::serde::With;
+
+                pub fn serialize<T: Label, S: Serializer>(sids: &[Sid<T>], serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.collect_seq(sids.iter().map(|&sid| With::<$crate::serde::$repr, T>::new(sid)))
+                }
+
+                pub fn deserialize<'de, T: Label, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Sid<T>>, D::Error> {
+                    let value = Vec::<With<$crate::serde::$repr, T>>::deserialize(deserializer)?;
+                    Ok(value.into_iter().map(|w| w.0).collect())
+                }
             }
         }
-        let value = deserializer.deserialize_str(SidVisitor)?;
-        let value = value.into_bytes();
-        Ok(Sid::<T>::from(value))
+    };
+}
+
+with_module!(
+    /// `#[serde(with = "sid::serde::as_uuid")]`: a hyphenated uuid string, or 16 bytes in binary formats.
+    #[cfg(feature = "uuid")]
+    as_uuid,
+    AsUuid
+);
+with_module!(
+    /// `#[serde(with = "sid::serde::labeled")]`: the labeled string, e.g. usr_0da0fa0e02cssbhkanf04c_srb0.
+    /// Deserializing accepts the bare form too, but rejects a mismatched label.
+    labeled,
+    Labeled
+);
+with_module!(
+    /// `#[serde(with = "sid::serde::as_bytes")]`: the raw 16 bytes, even in human readable formats.
+    as_bytes,
+    AsBytes
+);
+with_module!(
+    /// `#[serde(with = "sid::serde::as_u128")]`: a u128 number.
+    as_u128,
+    AsU128
+);
+
+with_module!(
+    /// `#[serde(with = "sid::serde::as_bson")]`: BSON binary subtype 4 (UUID), e.g. for Mongo `_id` fields.
+    /// Deserializing also accepts subtype 0 with 16 bytes, and strings.
+    #[cfg(feature = "bson")]
+    as_bson,
+    AsBson
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::{label, Label, Sid};
+    use serde::Deserialize;
+    #[cfg(feature = "uuid")]
+    use serde::Serialize;
+
+    label!(User, "usr");
+
+    #[test]
+    fn test_human_readable() {
+        let sid = Sid::<User>::from([1u8, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+        let json = serde_json::to_string(&sid).unwrap();
+        if cfg!(feature = "serialize_labeled") {
+            assert_eq!(json, "\"usr_01081g81860w40j2gb1g6g_w3rg\"");
+        } else {
+            assert_eq!(json, "\"01081g81860w40j2gb1g6g_w3rg\"");
+        }
+        assert_eq!(serde_json::from_str::<Sid<User>>(&json).unwrap(), sid);
+    }
+
+    #[test]
+    fn test_deserialize_checks_label() {
+        let sid = Sid::<User>::from([1u8, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+        let labeled = "\"usr_01081g81860w40j2gb1g6g_w3rg\"";
+        assert_eq!(serde_json::from_str::<Sid<User>>(labeled).unwrap(), sid);
+
+        let wrong = "\"team_01081g81860w40j2gb1g6g_w3rg\"";
+        let err = serde_json::from_str::<Sid<User>>(wrong).unwrap_err();
+        assert!(err.to_string().contains("expected \"usr\", found \"team\""), "{}", err);
+
+        let bare = "\"01081g81860w40j2gb1g6g_w3rg\"";
+        let result = serde_json::from_str::<Sid<User>>(bare);
+        if cfg!(feature = "strict_labels") {
+            let err = result.unwrap_err();
+            assert!(err.to_string().contains("Missing label while decoding sid: expected a \"usr\" prefix"), "{}", err);
+        } else {
+            assert_eq!(result.unwrap(), sid);
+        }
+        assert_eq!(serde_json::from_str::<Sid>(bare).unwrap(), sid.unlabel());
+    }
+
+    #[test]
+    fn test_binary_legacy_strings() {
+        use super::SidVisitor;
+        use serde::de::{value, Visitor};
+
+        // e.g. MessagePack hands strings to visit_str even though it isn't human readable.
+        let sid = Sid::<User>::from([1u8, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+        let bare = "01081g81860w40j2gb1g6g_w3rg";
+        assert_eq!(SidVisitor::<User>::new(false).visit_str::<value::Error>(bare).unwrap(), sid);
+        assert_eq!(SidVisitor::<User>::new(false).visit_bytes::<value::Error>(bare.as_bytes()).unwrap(), sid);
+        let result = SidVisitor::<User>::new(true).visit_str::<value::Error>(bare);
+        assert_eq!(result.is_err(), cfg!(feature = "strict_labels"));
+    }
+
+    #[test]
+    fn test_binary() {
+        let sid = Sid::<User>::new();
+        let bytes = postcard::to_allocvec(&sid).unwrap();
+        assert_eq!(bytes.len(), 1 + 16);
+        assert_eq!(&bytes[1..], sid.data());
+        assert_eq!(postcard::from_bytes::<Sid<User>>(&bytes).unwrap(), sid);
+    }
+
+    #[cfg(feature = "uuid")]
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Record {
+        #[serde(with = "crate::serde::as_uuid")]
+        uuid: Sid<User>,
+        #[serde(with = "crate::serde::labeled")]
+        labeled: Sid<User>,
+        #[serde(with = "crate::serde::as_bytes")]
+        bytes: Sid<User>,
+        #[serde(with = "crate::serde::as_u128")]
+        number: Sid<User>,
+        #[serde(with = "crate::serde::labeled::option")]
+        maybe: Option<Sid<User>>,
+        #[serde(with = "crate::serde::as_uuid::vec")]
+        many: Vec<Sid<User>>,
+    }
+
+    #[test]
+    #[cfg(feature = "uuid")]
+    fn test_with_helpers() {
+        let sid = Sid::<User>::from([1u8, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+        let record = Record {
+            uuid: sid,
+            labeled: sid,
+            bytes: sid,
+            number: sid,
+            maybe: Some(sid),
+            many: vec![sid, Sid::null()],
+        };
+        let string = serde_json::to_string(&record).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&string).unwrap();
+        assert_eq!(json["uuid"], "01020304-0506-0708-090a-0b0c0d0e0f10");
+        assert_eq!(json["labeled"], "usr_01081g81860w40j2gb1g6g_w3rg");
+        assert_eq!(json["bytes"], serde_json::json!(sid.data()));
+        assert_eq!(json["maybe"], "usr_01081g81860w40j2gb1g6g_w3rg");
+        assert_eq!(json["many"][1], "00000000-0000-0000-0000-000000000000");
+        assert!(string.contains(r#""number":1339673755198158349044581307228491536"#), "{}", string);
+        assert_eq!(serde_json::from_str::<Record>(&string).unwrap(), record);
+
+        let bytes = postcard::to_allocvec(&record).unwrap();
+        assert_eq!(postcard::from_bytes::<Record>(&bytes).unwrap(), record);
+    }
+
+    #[test]
+    fn test_labeled_rejects_wrong_label() {
+        #[derive(Deserialize)]
+        struct Body {
+            #[serde(with = "crate::serde::labeled", rename = "id")]
+            _id: Sid<User>,
+        }
+        let ok = r#"{"id": "01081g81860w40j2gb1g6g_w3rg"}"#;
+        assert!(serde_json::from_str::<Body>(ok).is_ok());
+        let wrong = r#"{"id": "team_01081g81860w40j2gb1g6g_w3rg"}"#;
+        assert!(serde_json::from_str::<Body>(wrong).is_err());
+    }
+
+    #[test]
+    fn test_binary_accepts_string() {
+        let sid = Sid::<User>::new();
+        let bytes = postcard::to_allocvec(&sid.to_string()).unwrap();
+        assert_eq!(postcard::from_bytes::<Sid<User>>(&bytes).unwrap(), sid);
     }
 }
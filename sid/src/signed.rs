@@ -0,0 +1,208 @@
+use std::fmt::{Debug, Display, Formatter};
+use std::str::FromStr;
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use sid_encode::{base32_decode, base32_encode};
+
+use crate::{decode_labeled, DecodeError, Label, NoLabel, Sid};
+
+const TAG_LENGTH: usize = 16;
+
+/// Signs and verifies `SignedSid` tokens with HMAC-SHA256. The tag covers the label, so a token
+/// minted for one label does not verify as another.
+#[derive(Clone)]
+pub struct SidSigner {
+    mac: Hmac<Sha256>,
+}
+
+impl SidSigner {
+    pub fn new(secret: &[u8]) -> Self {
+        Self {
+            mac: Hmac::new_from_slice(secret).expect("HMAC accepts keys of any length"),
+        }
+    }
+
+    fn mac<T: Label>(&self, sid: &Sid<T>) -> Hmac<Sha256> {
+        let mut mac = self.mac.clone();
+        mac.update(T::label().as_bytes());
+        mac.update(b"_");
+        mac.update(&sid.data);
+        mac
+    }
+
+    pub fn sign<T: Label>(&self, sid: Sid<T>) -> SignedSid<T> {
+        let digest = self.mac(&sid).finalize().into_bytes();
+        let mut tag = [0u8; TAG_LENGTH];
+        tag.copy_from_slice(&digest[..TAG_LENGTH]);
+        SignedSid { sid, tag }
+    }
+
+    /// Check the token's tag in constant time, returning the inner sid if it is authentic.
+    pub fn verify<T: Label>(&self, token: &SignedSid<T>) -> Result<Sid<T>, InvalidSignature> {
+        self.mac(&token.sid)
+            .verify_truncated_left(&token.tag)
+            .map_err(|_| InvalidSignature)?;
+        Ok(token.sid)
+    }
+}
+
+impl Debug for SidSigner {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SidSigner")
+    }
+}
+
+/// A sid with an HMAC tag, e.g. usr_0da0fa0e02cssbhkanf04c_srb0_5g1x9c0wqk3m8r2dtbnv7e_h4aj
+///
+/// Parsing only checks the format. Use `SidSigner::verify` to get at the sid.
+pub struct SignedSid<T = NoLabel> {
+    sid: Sid<T>,
+    tag: [u8; TAG_LENGTH],
+}
+
+impl<T> SignedSid<T> {
+    /// The sid the token claims to carry. It has not been verified.
+    pub fn unverified(&self) -> Sid<T> {
+        self.sid
+    }
+}
+
+impl<T> Clone for SignedSid<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for SignedSid<T> {}
+
+impl<T> PartialEq for SignedSid<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.sid == other.sid && self.tag == other.tag
+    }
+}
+
+impl<T> Eq for SignedSid<T> {}
+
+impl<T: Label> Display for SignedSid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let label = T::label();
+        if !label.is_empty() {
+            f.write_str(label)?;
+            f.write_str("_")?;
+        }
+        f.write_str(&base32_encode(self.sid.data))?;
+        f.write_str("_")?;
+        f.write_str(&base32_encode(self.tag))
+    }
+}
+
+impl<T: Label> Debug for SignedSid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl<T: Label> FromStr for SignedSid<T> {
+    type Err = DecodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some(split) = s.len().checked_sub(27) else {
+            return Err(DecodeError::InvalidLength);
+        };
+        if !s.is_char_boundary(split) {
+            return Err(DecodeError::InvalidLength);
+        }
+        let (sid, tag) = s.split_at(split);
+        let sid = sid.strip_suffix('_').ok_or(DecodeError::NoSeparator)?;
+        Ok(Self {
+            sid: Sid::from(decode_labeled::<T>(sid)?),
+            tag: base32_decode(tag)?,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: Label> serde::Serialize for SignedSid<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Label> serde::Deserialize<'de> for SignedSid<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidSignature;
+
+impl Display for InvalidSignature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid signature for sid")
+    }
+}
+
+impl std::error::Error for InvalidSignature {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::label;
+
+    label!(User, "usr");
+    label!(Team, "team");
+
+    #[test]
+    fn test_sign_verify() {
+        let signer = SidSigner::new(b"secret");
+        let sid = Sid::<User>::new();
+        let token = signer.sign(sid);
+        assert_eq!(signer.verify(&token), Ok(sid));
+        assert_eq!(SidSigner::new(b"other").verify(&token), Err(InvalidSignature));
+    }
+
+    #[test]
+    fn test_format() {
+        let signer = SidSigner::new(b"secret");
+        let token = signer.sign(Sid::<User>::null());
+        let s = token.to_string();
+        assert!(s.starts_with("usr_0000000000000000000000_0000_"), "{}", s);
+        assert_eq!(s.len(), 4 + 27 + 1 + 27);
+        assert!(s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'));
+
+        let parsed: SignedSid<User> = s.parse().unwrap();
+        assert_eq!(parsed, token);
+        assert!(signer.verify(&parsed).is_ok());
+        assert!(matches!(
+            s.parse::<SignedSid<Team>>(),
+            Err(DecodeError::InvalidLabel { .. })
+        ));
+    }
+
+    #[test]
+    fn test_tampered() {
+        let signer = SidSigner::new(b"secret");
+        let token = signer.sign(Sid::<User>::null()).to_string();
+        let tampered = token.replacen("usr_0000", "usr_0001", 1);
+        let parsed: SignedSid<User> = tampered.parse().unwrap();
+        assert_eq!(signer.verify(&parsed), Err(InvalidSignature));
+    }
+
+    #[test]
+    fn test_label_is_signed() {
+        let signer = SidSigner::new(b"secret");
+        let user = signer.sign(Sid::<User>::null());
+        let team = signer.sign(Sid::<Team>::null());
+        assert_ne!(user.tag, team.tag);
+    }
+}
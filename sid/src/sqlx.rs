@@ -1,2 +1,43 @@
+#[cfg(feature = "sqlx-mysql")]
+mod mysql;
+#[cfg(feature = "sqlx-pg-ext")]
+mod pg_ext;
+#[cfg(feature = "sqlx-sqlite")]
+mod sqlite;
+#[cfg(feature = "sqlx-postgres")]
+mod uuid;
 
-mod uuid;
\ No newline at end of file
+#[cfg(feature = "sqlx-pg-ext")]
+pub use pg_ext::PgSid;
+
+/// SQLite and MySQL store sids as 16 byte blobs, but we also accept text columns holding the bare or labeled form,
+/// e.g. ones written with `sqlx::types::Text<Sid<T>>`.
+#[cfg(any(feature = "sqlx-sqlite", feature = "sqlx-mysql"))]
+fn decode_blob_or_text<T: crate::Label>(bytes: &[u8]) -> Result<[u8; 16], ::sqlx::error::BoxDynError> {
+    if let Ok(data) = <[u8; 16]>::try_from(bytes) {
+        return Ok(data);
+    }
+    let text = std::str::from_utf8(bytes)?;
+    Ok(crate::decode_labeled::<T>(text)?)
+}
+
+#[cfg(all(test, any(feature = "sqlx-sqlite", feature = "sqlx-mysql")))]
+mod tests {
+    use super::decode_blob_or_text;
+    use crate::{label, DecodeError, Label, Sid};
+
+    label!(User, "usr");
+    label!(Team, "team");
+
+    #[test]
+    fn test_decode_blob_or_text() {
+        let sid = Sid::<User>::new();
+        assert_eq!(decode_blob_or_text::<User>(sid.data()).unwrap(), *sid.data());
+        assert_eq!(decode_blob_or_text::<User>(sid.to_string().as_bytes()).unwrap(), *sid.data());
+        assert_eq!(decode_blob_or_text::<User>(format!("{:?}", sid).as_bytes()).unwrap(), *sid.data());
+
+        let err = decode_blob_or_text::<Team>(format!("{:?}", sid).as_bytes()).unwrap_err();
+        assert!(matches!(err.downcast_ref(), Some(DecodeError::InvalidLabel { .. })), "{}", err);
+        assert!(decode_blob_or_text::<User>(&sid.data()[..15]).is_err());
+    }
+}
@@ -0,0 +1,42 @@
+use crate::{Label, Sid};
+use ::sqlx::encode::IsNull;
+use ::sqlx::error::BoxDynError;
+/// Encode sid as BINARY(16) in the database
+use ::sqlx::mysql::{MySql, MySqlTypeInfo, MySqlValueRef};
+
+impl<T> ::sqlx::Encode<'_, MySql> for Sid<T> {
+    fn encode_by_ref(&self, buf: &mut Vec<u8>) -> Result<IsNull, BoxDynError> {
+        <&[u8] as ::sqlx::Encode<MySql>>::encode(&self.data[..], buf)
+    }
+}
+
+impl<T: Label> ::sqlx::Decode<'_, MySql> for Sid<T> {
+    fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
+        let bytes = <&[u8] as ::sqlx::Decode<MySql>>::decode(value)?;
+        Ok(Sid::from(super::decode_blob_or_text::<T>(bytes)?))
+    }
+}
+
+impl<T> ::sqlx::Type<MySql> for Sid<T> {
+    fn type_info() -> MySqlTypeInfo {
+        <[u8] as ::sqlx::Type<MySql>>::type_info()
+    }
+
+    fn compatible(ty: &MySqlTypeInfo) -> bool {
+        <[u8] as ::sqlx::Type<MySql>>::compatible(ty) || <str as ::sqlx::Type<MySql>>::compatible(ty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Sid;
+    use ::sqlx::mysql::MySql;
+    use ::sqlx::Type;
+
+    #[test]
+    fn test_type_compatibility() {
+        assert!(<Sid as Type<MySql>>::compatible(&<Vec<u8> as Type<MySql>>::type_info()));
+        assert!(<Sid as Type<MySql>>::compatible(&<String as Type<MySql>>::type_info()));
+        assert!(!<Sid as Type<MySql>>::compatible(&<i64 as Type<MySql>>::type_info()));
+    }
+}
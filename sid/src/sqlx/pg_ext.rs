@@ -0,0 +1,163 @@
+use std::fmt::{Debug, Formatter};
+
+use crate::{check_pg_ext_label, decode_pg_ext, pg_ext_label, DecodeError, Label, NoLabel, Sid};
+use ::sqlx::encode::IsNull;
+use ::sqlx::error::BoxDynError;
+use ::sqlx::postgres::{
+    PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres,
+};
+use ::sqlx::types::Uuid;
+
+/// A sid that binds as the postgres extension's `sid` type, label included, instead of `uuid`. Use it for columns
+/// of that type, e.g. `.bind(PgSid(id))` or `query_scalar::<_, PgSid<User>>(...)`. Decoding also accepts `uuid`.
+pub struct PgSid<T = NoLabel>(pub Sid<T>);
+
+impl<T> Clone for PgSid<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for PgSid<T> {}
+
+impl<T> PartialEq for PgSid<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T> Eq for PgSid<T> {}
+
+impl<T: Label> Debug for PgSid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+impl<T> From<Sid<T>> for PgSid<T> {
+    fn from(sid: Sid<T>) -> Self {
+        Self(sid)
+    }
+}
+
+impl<T> From<PgSid<T>> for Sid<T> {
+    fn from(sid: PgSid<T>) -> Self {
+        sid.0
+    }
+}
+
+/// The text form is the extension's output, e.g. usr_0da0fa0e02cssbhkanf04c_srb0, or a uuid.
+fn decode_text<T: Label>(text: &str) -> Result<[u8; 16], BoxDynError> {
+    if let Ok(uuid) = Uuid::parse_str(text) {
+        return Ok(uuid.into_bytes());
+    }
+    let Some(split) = text.len().checked_sub(27) else {
+        return Err(DecodeError::InvalidLength.into());
+    };
+    if !text.is_char_boundary(split) {
+        return Err(DecodeError::InvalidLength.into());
+    }
+    let (prefix, encoded) = text.split_at(split);
+    if !prefix.is_empty() {
+        let label = prefix.strip_suffix('_').ok_or(DecodeError::NoSeparator)?;
+        check_pg_ext_label::<T>(label.as_bytes())?;
+    }
+    Ok(sid_encode::base32_decode(encoded)?)
+}
+
+impl<T: Label> ::sqlx::Encode<'_, Postgres> for PgSid<T> {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        buf.extend_from_slice(&self.0.data);
+        buf.extend_from_slice(pg_ext_label::<T>());
+        Ok(IsNull::No)
+    }
+}
+
+impl<T: Label> ::sqlx::Decode<'_, Postgres> for PgSid<T> {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        let data = match value.format() {
+            // a `uuid` column is just the 16 bytes, which is also an unlabeled `sid`
+            PgValueFormat::Binary => decode_pg_ext::<T>(value.as_bytes()?)?,
+            PgValueFormat::Text => decode_text::<T>(value.as_str()?)?,
+        };
+        Ok(PgSid(Sid::from(data)))
+    }
+}
+
+impl<T> ::sqlx::Type<Postgres> for PgSid<T> {
+    /// The extension's type has no fixed OID, so sqlx looks it up by name on first use.
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("sid")
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        *ty == <Self as ::sqlx::Type<Postgres>>::type_info()
+            || <Uuid as ::sqlx::Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl<T> PgHasArrayType for PgSid<T> {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("_sid")
+    }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        *ty == <Self as PgHasArrayType>::array_type_info()
+            || <Uuid as PgHasArrayType>::array_compatible(ty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{label, NoLabel};
+    use ::sqlx::{Type, TypeInfo};
+
+    label!(User, "usr");
+    label!(Team, "team");
+    label!(Organization, "organization");
+
+    #[test]
+    fn test_type_info() {
+        assert_eq!(<PgSid<User> as Type<Postgres>>::type_info().name(), "sid");
+        assert_eq!(<Vec<PgSid<User>> as Type<Postgres>>::type_info().name(), "_sid");
+        assert!(<PgSid<User> as Type<Postgres>>::compatible(
+            &<Uuid as Type<Postgres>>::type_info()
+        ));
+        // the plain Sid still binds as uuid alongside it
+        assert_eq!(<Sid<User> as Type<Postgres>>::type_info().name(), "UUID");
+    }
+
+    #[test]
+    fn test_binary() {
+        let sid = Sid::<User>::new();
+        let mut buf = PgArgumentBuffer::default();
+        let _ = ::sqlx::Encode::<Postgres>::encode_by_ref(&PgSid(sid), &mut buf).unwrap();
+        assert_eq!(buf.len(), 16 + 3);
+        assert_eq!(&buf[16..], b"usr");
+
+        assert_eq!(decode_pg_ext::<User>(&buf).unwrap(), sid.data);
+        assert_eq!(decode_pg_ext::<User>(&sid.data).unwrap(), sid.data);
+        assert!(matches!(
+            decode_pg_ext::<Team>(&buf),
+            Err(DecodeError::InvalidLabel { .. })
+        ));
+        assert!(decode_pg_ext::<NoLabel>(&sid.data).is_ok());
+    }
+
+    #[test]
+    fn test_truncated_label() {
+        assert_eq!(pg_ext_label::<Organization>(), b"organiza");
+        assert!(decode_pg_ext::<Organization>(&[&[0; 16][..], b"organiza"].concat()).is_ok());
+        assert!(decode_text::<Organization>("organiza_0000000000000000000000_0000").is_ok());
+    }
+
+    #[test]
+    fn test_text() {
+        let sid = Sid::<User>::new();
+        assert_eq!(decode_text::<User>(&sid.to_string()).unwrap(), sid.data);
+        assert_eq!(decode_text::<User>(&format!("{:?}", sid)).unwrap(), sid.data);
+        assert_eq!(decode_text::<User>(&sid.uuid().to_string()).unwrap(), sid.data);
+        assert!(decode_text::<Team>(&format!("{:?}", sid)).is_err());
+    }
+}
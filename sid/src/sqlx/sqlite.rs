@@ -0,0 +1,111 @@
+use crate::{Label, Sid};
+use ::sqlx::encode::IsNull;
+use ::sqlx::error::BoxDynError;
+/// Encode sid as a 16 byte blob in the database
+use ::sqlx::sqlite::{Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef};
+use std::borrow::Cow;
+
+impl<'q, T> ::sqlx::Encode<'q, Sqlite> for Sid<T> {
+    fn encode_by_ref(&self, args: &mut Vec<SqliteArgumentValue<'q>>) -> Result<IsNull, BoxDynError> {
+        args.push(SqliteArgumentValue::Blob(Cow::Owned(self.data.to_vec())));
+        Ok(IsNull::No)
+    }
+}
+
+impl<T: Label> ::sqlx::Decode<'_, Sqlite> for Sid<T> {
+    fn decode(value: SqliteValueRef<'_>) -> Result<Self, BoxDynError> {
+        let bytes = <&[u8] as ::sqlx::Decode<Sqlite>>::decode(value)?;
+        Ok(Sid::from(super::decode_blob_or_text::<T>(bytes)?))
+    }
+}
+
+impl<T> ::sqlx::Type<Sqlite> for Sid<T> {
+    fn type_info() -> SqliteTypeInfo {
+        <[u8] as ::sqlx::Type<Sqlite>>::type_info()
+    }
+
+    fn compatible(ty: &SqliteTypeInfo) -> bool {
+        <[u8] as ::sqlx::Type<Sqlite>>::compatible(ty) || <str as ::sqlx::Type<Sqlite>>::compatible(ty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{label, Label, Sid};
+    use ::sqlx::types::Text;
+    use ::sqlx::{Connection, SqliteConnection};
+
+    label!(User, "usr");
+
+    #[tokio::test]
+    async fn test_blob() {
+        let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+        ::sqlx::query("CREATE TABLE users (id BLOB PRIMARY KEY)")
+            .execute(&mut conn)
+            .await
+            .unwrap();
+        let mut sids = Sid::<User>::batch(3);
+        sids.reverse();
+        for sid in &sids {
+            ::sqlx::query("INSERT INTO users (id) VALUES (?)")
+                .bind(sid)
+                .execute(&mut conn)
+                .await
+                .unwrap();
+        }
+        let length: i64 = ::sqlx::query_scalar("SELECT length(id) FROM users LIMIT 1")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(length, 16);
+
+        let stored: Vec<Sid<User>> = ::sqlx::query_scalar("SELECT id FROM users ORDER BY id")
+            .fetch_all(&mut conn)
+            .await
+            .unwrap();
+        sids.sort();
+        assert_eq!(stored, sids);
+    }
+
+    #[tokio::test]
+    async fn test_text() {
+        let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+        ::sqlx::query("CREATE TABLE users (id TEXT PRIMARY KEY)")
+            .execute(&mut conn)
+            .await
+            .unwrap();
+        let sid = Sid::<User>::new();
+        ::sqlx::query("INSERT INTO users (id) VALUES (?)")
+            .bind(Text(sid))
+            .execute(&mut conn)
+            .await
+            .unwrap();
+        let text: String = ::sqlx::query_scalar("SELECT id FROM users")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(text, sid.to_string());
+
+        let stored: Sid<User> = ::sqlx::query_scalar("SELECT id FROM users")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(stored, sid);
+
+        ::sqlx::query("UPDATE users SET id = ?")
+            .bind(format!("{:?}", sid))
+            .execute(&mut conn)
+            .await
+            .unwrap();
+        let stored: Sid<User> = ::sqlx::query_scalar("SELECT id FROM users")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(stored, sid);
+        let Text(stored): Text<Sid<User>> = ::sqlx::query_scalar("SELECT id FROM users")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(stored, sid);
+    }
+}
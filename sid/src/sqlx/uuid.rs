@@ -1,32 +1,108 @@
-use crate::Sid;
+use crate::{decode_labeled, Label, Sid};
 use ::sqlx::encode::IsNull;
 /// Encode sid as uuid in the database
-use ::sqlx::postgres::{PgArgumentBuffer, PgValueRef, Postgres};
+use ::sqlx::postgres::{
+    PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres,
+};
 use ::sqlx::types::Uuid;
 
 impl<T> ::sqlx::Encode<'_, Postgres> for Sid<T> {
     fn encode_by_ref(
         &self,
         buf: &mut PgArgumentBuffer,
-    ) -> Result<IsNull, Box<(dyn std::error::Error + Send + Sync + 'static)>> {
+    ) -> Result<IsNull, Box<dyn std::error::Error + Send + Sync + 'static>> {
         let uuid = self.uuid();
         <Uuid as sqlx::Encode<Postgres>>::encode(uuid, buf)
     }
 }
 
-impl<T> ::sqlx::Decode<'_, Postgres> for Sid<T> {
+impl<T: Label> ::sqlx::Decode<'_, Postgres> for Sid<T> {
     fn decode(value: PgValueRef<'_>) -> Result<Self, sqlx::error::BoxDynError> {
-        let uuid = Uuid::from_slice(value.as_bytes()?)?;
-        Ok(Sid::<T>::from(uuid))
+        let text = match value.format() {
+            PgValueFormat::Binary => {
+                let bytes = value.as_bytes()?;
+                if let Ok(data) = <[u8; 16]>::try_from(bytes) {
+                    return Ok(Sid::from(data));
+                }
+                // a text column, in binary format, is just the utf-8 bytes
+                std::str::from_utf8(bytes)?
+            }
+            // e.g. results of the simple query protocol, or a text column
+            PgValueFormat::Text => value.as_str()?,
+        };
+        decode_text(text)
     }
 }
 
+/// A uuid, or the bare or labeled form, e.g. the extension's `sid` cast to text.
+fn decode_text<T: Label>(text: &str) -> Result<Sid<T>, sqlx::error::BoxDynError> {
+    if text.len() == 36 {
+        return Ok(Sid::from(Uuid::parse_str(text)?));
+    }
+    Ok(Sid::from(decode_labeled::<T>(text)?))
+}
+
 impl<T> ::sqlx::Type<Postgres> for Sid<T> {
     fn type_info() -> <Postgres as sqlx::Database>::TypeInfo {
         <Uuid as sqlx::Type<Postgres>>::type_info()
     }
 
     fn compatible(ty: &<Postgres as sqlx::Database>::TypeInfo) -> bool {
-        <Uuid as ::sqlx::Type<Postgres>>::compatible(ty)
+        <Uuid as ::sqlx::Type<Postgres>>::compatible(ty) || <str as ::sqlx::Type<Postgres>>::compatible(ty)
+    }
+}
+
+/// Lets `Vec<Sid<T>>` and `&[Sid<T>]` (and their `Option` variants) bind as `uuid[]`, e.g. `WHERE id = ANY($1)`.
+impl<T> PgHasArrayType for Sid<T> {
+    fn array_type_info() -> PgTypeInfo {
+        <Uuid as PgHasArrayType>::array_type_info()
+    }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        <Uuid as PgHasArrayType>::array_compatible(ty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::decode_text;
+    use crate::{label, DecodeError, Label, Sid};
+    use ::sqlx::postgres::Postgres;
+    use ::sqlx::types::Uuid;
+    use ::sqlx::{Encode, Type};
+
+    label!(User, "usr");
+    label!(Team, "team");
+
+    fn assert_encode<'q, T: Encode<'q, Postgres> + Type<Postgres>>(_: &T) {}
+
+    #[test]
+    fn test_array_types() {
+        let sids = vec![Sid::<User>::new(), Sid::<User>::new()];
+        assert_encode(&sids);
+        assert_encode(&sids.as_slice());
+        assert_encode(&vec![Some(sids[0]), None]);
+        assert_eq!(
+            <Vec<Sid<User>> as Type<Postgres>>::type_info(),
+            <Vec<Uuid> as Type<Postgres>>::type_info()
+        );
+        assert_eq!(
+            <&[Option<Sid<User>>] as Type<Postgres>>::type_info(),
+            <Vec<Uuid> as Type<Postgres>>::type_info()
+        );
+    }
+
+    #[test]
+    fn test_text() {
+        let sid = Sid::<User>::new();
+        assert_eq!(decode_text::<User>(&format!("{:?}", sid)).unwrap(), sid);
+        assert_eq!(decode_text::<User>(&sid.to_string()).unwrap(), sid);
+        assert_eq!(decode_text::<User>(&sid.uuid().to_string()).unwrap(), sid);
+
+        let err = decode_text::<Team>(&format!("{:?}", sid)).unwrap_err();
+        assert!(matches!(err.downcast_ref(), Some(DecodeError::InvalidLabel { .. })), "{}", err);
+
+        assert!(<Sid<User> as Type<Postgres>>::compatible(&<String as Type<Postgres>>::type_info()));
+        assert!(!<Sid<User> as Type<Postgres>>::compatible(&<i64 as Type<Postgres>>::type_info()));
     }
 }
@@ -0,0 +1,83 @@
+//! Helpers for passing sids in gRPC metadata. ASCII headers carry the labeled form, e.g.
+//! `x-user-id: usr_0da0fa0e02cssbhkanf04c_srb0`, and `-bin` headers carry the 16 raw bytes.
+
+use tonic::metadata::{AsciiMetadataValue, BinaryMetadataValue, MetadataMap};
+use tonic::Status;
+
+use crate::{decode_labeled, Label, Sid};
+
+/// Insert the labeled form of `sid` under `key`.
+///
+/// Panics if `key` isn't a valid ASCII metadata key, like `MetadataMap::insert`.
+pub fn insert<T: Label>(metadata: &mut MetadataMap, key: &'static str, sid: Sid<T>) {
+    let value = AsciiMetadataValue::try_from(format!("{:?}", sid)).expect("sids are ascii");
+    metadata.insert(key, value);
+}
+
+/// Insert the 16 bytes of `sid` under `key`, which must end in `-bin`.
+pub fn insert_bin<T>(metadata: &mut MetadataMap, key: &'static str, sid: Sid<T>) {
+    metadata.insert_bin(key, BinaryMetadataValue::from_bytes(&sid.data));
+}
+
+/// Read a sid in the labeled or bare form from `key`. A malformed value, or one with the wrong label, is an
+/// `InvalidArgument` status, so handlers can use `?`.
+pub fn get<T: Label>(metadata: &MetadataMap, key: &str) -> Result<Option<Sid<T>>, Status> {
+    let Some(value) = metadata.get(key) else {
+        return Ok(None);
+    };
+    let value = value
+        .to_str()
+        .map_err(|e| Status::invalid_argument(format!("{}: {}", key, e)))?;
+    decode_labeled::<T>(value)
+        .map(|data| Some(Sid::from(data)))
+        .map_err(|e| Status::invalid_argument(format!("{}: {}", key, e)))
+}
+
+/// Read the 16 bytes of a sid from `key`, which must end in `-bin`.
+pub fn get_bin<T>(metadata: &MetadataMap, key: &str) -> Result<Option<Sid<T>>, Status> {
+    let Some(value) = metadata.get_bin(key) else {
+        return Ok(None);
+    };
+    let bytes = value
+        .to_bytes()
+        .map_err(|e| Status::invalid_argument(format!("{}: {}", key, e)))?;
+    Sid::try_from(bytes.as_ref())
+        .map(Some)
+        .map_err(|e| Status::invalid_argument(format!("{}: {}", key, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::label;
+    use tonic::Code;
+
+    label!(User, "usr");
+    label!(Team, "team");
+
+    #[test]
+    fn test_ascii() {
+        let sid = Sid::<User>::new();
+        let mut metadata = MetadataMap::new();
+        insert(&mut metadata, "x-user-id", sid);
+        assert_eq!(metadata.get("x-user-id").unwrap(), format!("{:?}", sid).as_str());
+        assert_eq!(get::<User>(&metadata, "x-user-id").unwrap(), Some(sid));
+        assert_eq!(get::<User>(&metadata, "x-team-id").unwrap(), None);
+
+        let err = get::<Team>(&metadata, "x-user-id").unwrap_err();
+        assert_eq!(err.code(), Code::InvalidArgument);
+        assert!(err.message().starts_with("x-user-id: Invalid label"), "{}", err.message());
+    }
+
+    #[test]
+    fn test_binary() {
+        let sid = Sid::<User>::new();
+        let mut metadata = MetadataMap::new();
+        insert_bin(&mut metadata, "x-user-id-bin", sid);
+        assert_eq!(get_bin::<User>(&metadata, "x-user-id-bin").unwrap(), Some(sid));
+
+        metadata.insert_bin("x-team-id-bin", BinaryMetadataValue::from_bytes(&[0; 15]));
+        let err = get_bin::<User>(&metadata, "x-team-id-bin").unwrap_err();
+        assert_eq!(err.code(), Code::InvalidArgument);
+    }
+}
@@ -0,0 +1,47 @@
+use std::borrow::Cow;
+
+use utoipa::openapi::schema::{ObjectBuilder, Schema, Type};
+use utoipa::openapi::RefOr;
+use utoipa::{PartialSchema, ToSchema};
+
+use crate::{schema_description, schema_example, schema_name, Label, Sid};
+
+impl<T: Label> PartialSchema for Sid<T> {
+    fn schema() -> RefOr<Schema> {
+        ObjectBuilder::new()
+            .schema_type(Type::String)
+            .pattern(Some(Sid::<T>::pattern()))
+            .description(Some(schema_description::<T>()))
+            .examples([schema_example::<T>()])
+            .into()
+    }
+}
+
+impl<T: Label> ToSchema for Sid<T> {
+    fn name() -> Cow<'static, str> {
+        schema_name::<T>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use utoipa::openapi::schema::Schema;
+    use utoipa::openapi::RefOr;
+    use utoipa::{PartialSchema, ToSchema};
+
+    use crate::{label, Label, Sid};
+
+    label!(User, "usr");
+
+    #[test]
+    fn test_schema() {
+        assert_eq!(Sid::<User>::name(), "Sid_usr");
+        let RefOr::T(Schema::Object(object)) = Sid::<User>::schema() else {
+            panic!("expected an inline object schema");
+        };
+        assert_eq!(object.pattern, Some(Sid::<User>::pattern()));
+        assert!(object.description.unwrap().contains("usr"));
+        let example = object.examples[0].as_str().unwrap().to_string();
+        assert!(example.ends_with("0000000000000000000000_0000"));
+    }
+}
@@ -0,0 +1,279 @@
+//! The JavaScript API, via wasm-bindgen. Labels are only known at runtime in JS, so the `Sid` class carries its
+//! label as a string instead of a type parameter. String forms are typed with template literals by tsify.
+#![cfg(target_arch = "wasm32")]
+
+use std::cmp::Ordering;
+
+use sid_encode::{base32_decode, base32_encode, SHORT_LENGTH};
+use tsify::Tsify;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+use sid::{NoLabel, Sid};
+
+/// The bare form, e.g. `0da0fa0e02cssbhkanf04c_srb0`.
+#[derive(Tsify)]
+pub struct BareSid(#[tsify(type = "`${string}_${string}`")] pub String);
+
+/// The labeled form, e.g. `usr_0da0fa0e02cssbhkanf04c_srb0`. Narrow it per label, e.g.
+/// ``type UserId = `usr_${BareSid}` ``.
+#[derive(Tsify)]
+pub struct LabeledSid(#[tsify(type = "`${string}_${BareSid}`")] pub String);
+
+#[derive(Tsify)]
+pub struct UuidString(#[tsify(type = "`${string}-${string}-${string}-${string}-${string}`")] pub String);
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "BareSid | LabeledSid")]
+    pub type JsSidString;
+
+    #[wasm_bindgen(typescript_type = "UuidString")]
+    pub type JsUuidString;
+}
+
+#[wasm_bindgen(js_name = Sid)]
+#[derive(Clone)]
+pub struct JsSid {
+    sid: Sid,
+    label: String,
+}
+
+#[wasm_bindgen(js_class = Sid)]
+impl JsSid {
+    /// Generate a new sid, optionally with a label, e.g. `new Sid("usr")`.
+    #[wasm_bindgen(constructor)]
+    pub fn new(label: Option<String>) -> JsSid {
+        JsSid {
+            sid: Sid::<NoLabel>::new(),
+            label: label.unwrap_or_default(),
+        }
+    }
+
+    /// Same as the constructor.
+    pub fn generate(label: Option<String>) -> JsSid {
+        JsSid::new(label)
+    }
+
+    /// Parse the labeled or bare form. If `label` is given, a labeled input must match it, and a bare input
+    /// takes it.
+    pub fn parse(s: &str, label: Option<String>) -> Result<JsSid, JsError> {
+        let (found, data) = split_labeled(s)?;
+        let label = match label {
+            Some(label) if !found.is_empty() && found != label => {
+                return Err(JsError::new(&format!(
+                    "Invalid label while decoding sid: expected {:?}, found {:?}",
+                    label, found
+                )));
+            }
+            Some(label) => label,
+            None => found.to_string(),
+        };
+        Ok(JsSid {
+            sid: Sid::from(data),
+            label,
+        })
+    }
+
+    /// Parse a uuid, hyphenated or not.
+    #[wasm_bindgen(js_name = fromUuid)]
+    pub fn from_uuid(uuid: &str, label: Option<String>) -> Result<JsSid, JsError> {
+        Ok(JsSid {
+            sid: Sid::from(parse_uuid(uuid)?),
+            label: label.unwrap_or_default(),
+        })
+    }
+
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8], label: Option<String>) -> Result<JsSid, JsError> {
+        Ok(JsSid {
+            sid: Sid::try_from(bytes)?,
+            label: label.unwrap_or_default(),
+        })
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn label(&self) -> Option<String> {
+        (!self.label.is_empty()).then(|| self.label.clone())
+    }
+
+    /// The labeled form, or the bare form if there's no label.
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_js_string(&self) -> JsSidString {
+        JsValue::from_str(&self.labeled()).unchecked_into()
+    }
+
+    #[wasm_bindgen(js_name = toJSON)]
+    pub fn to_json(&self) -> JsSidString {
+        self.to_js_string()
+    }
+
+    /// The bare form, without the label.
+    pub fn bare(&self) -> JsSidString {
+        JsValue::from_str(&self.sid.to_string()).unchecked_into()
+    }
+
+    #[wasm_bindgen(js_name = toUuid)]
+    pub fn to_uuid(&self) -> JsUuidString {
+        JsValue::from_str(&format_uuid(self.sid.data())).unchecked_into()
+    }
+
+    /// Only the short suffix of the sid, with label, e.g. `usr_srb0`.
+    pub fn short(&self) -> String {
+        let encoded = base32_encode(*self.sid.data());
+        let suffix = &encoded[SHORT_LENGTH + 1..];
+        if self.label.is_empty() {
+            suffix.to_string()
+        } else {
+            format!("{}_{}", self.label, suffix)
+        }
+    }
+
+    /// Milliseconds since the unix epoch, for `new Date(sid.timestamp())`.
+    pub fn timestamp(&self) -> f64 {
+        self.sid.timestamp() as f64
+    }
+
+    pub fn bytes(&self) -> Vec<u8> {
+        self.sid.data().to_vec()
+    }
+
+    /// Compares the 16 bytes, ignoring labels, so `ids.sort(Sid.compare)` sorts by creation time.
+    pub fn compare(a: &JsSid, b: &JsSid) -> i32 {
+        ordering(a.sid.cmp(&b.sid))
+    }
+
+    pub fn equals(&self, other: &JsSid) -> bool {
+        self.sid == other.sid
+    }
+}
+
+impl JsSid {
+    fn labeled(&self) -> String {
+        if self.label.is_empty() {
+            self.sid.to_string()
+        } else {
+            format!("{}_{}", self.label, self.sid)
+        }
+    }
+}
+
+/// Generate a sid string, optionally labeled.
+#[wasm_bindgen]
+pub fn generate(label: Option<String>) -> JsSidString {
+    JsSid::new(label).to_js_string()
+}
+
+/// Convert the labeled or bare form to a uuid.
+#[wasm_bindgen(js_name = toUuid)]
+pub fn to_uuid(s: &str) -> Result<JsUuidString, JsError> {
+    Ok(JsSid::parse(s, None)?.to_uuid())
+}
+
+/// Convert a uuid to a sid string, optionally labeled.
+#[wasm_bindgen(js_name = fromUuid)]
+pub fn from_uuid(uuid: &str, label: Option<String>) -> Result<JsSidString, JsError> {
+    Ok(JsSid::from_uuid(uuid, label)?.to_js_string())
+}
+
+#[wasm_bindgen]
+pub fn short(s: &str) -> Result<String, JsError> {
+    Ok(JsSid::parse(s, None)?.short())
+}
+
+#[wasm_bindgen]
+pub fn timestamp(s: &str) -> Result<f64, JsError> {
+    Ok(JsSid::parse(s, None)?.timestamp())
+}
+
+/// Compare two sid strings by creation time, ignoring labels.
+#[wasm_bindgen]
+pub fn compare(a: &str, b: &str) -> Result<i32, JsError> {
+    Ok(JsSid::compare(&JsSid::parse(a, None)?, &JsSid::parse(b, None)?))
+}
+
+fn ordering(ordering: Ordering) -> i32 {
+    match ordering {
+        Ordering::Less => -1,
+        Ordering::Equal => 0,
+        Ordering::Greater => 1,
+    }
+}
+
+/// Like `decode_labeled`, but returns whatever label it finds.
+fn split_labeled(s: &str) -> Result<(&str, [u8; 16]), JsError> {
+    let Some(split) = s.len().checked_sub(27) else {
+        return Err(sid_encode::DecodeError::InvalidLength.into());
+    };
+    if split == 0 {
+        return Ok(("", base32_decode(s)?));
+    }
+    if !s.is_char_boundary(split) {
+        return Err(sid_encode::DecodeError::InvalidLength.into());
+    }
+    let (prefix, encoded) = s.split_at(split);
+    let label = prefix
+        .strip_suffix('_')
+        .ok_or(sid_encode::DecodeError::NoSeparator)?;
+    Ok((label, base32_decode(encoded)?))
+}
+
+fn parse_uuid(uuid: &str) -> Result<[u8; 16], JsError> {
+    let hex: String = match uuid.len() {
+        32 => uuid.to_string(),
+        36 if [8, 13, 18, 23].iter().all(|&i| uuid.as_bytes()[i] == b'-') => uuid.replace('-', ""),
+        _ => return Err(JsError::new(&format!("Invalid uuid: {:?}", uuid))),
+    };
+    if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(JsError::new(&format!("Invalid uuid: {:?}", uuid)));
+    }
+    let value = u128::from_str_radix(&hex, 16).map_err(|_| JsError::new(&format!("Invalid uuid: {:?}", uuid)))?;
+    Ok(value.to_be_bytes())
+}
+
+fn format_uuid(data: &[u8; 16]) -> String {
+    let hex = format!("{:032x}", u128::from_be_bytes(*data));
+    format!("{}-{}-{}-{}-{}", &hex[..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..])
+}
+
+#[cfg(test)]
+mod tests {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+
+    #[wasm_bindgen_test]
+    fn test_parse() {
+        let sid = JsSid::new(Some("usr".to_string()));
+        let labeled = sid.labeled();
+        assert!(labeled.starts_with("usr_"));
+
+        let parsed = JsSid::parse(&labeled, None).unwrap();
+        assert_eq!(parsed.label(), Some("usr".to_string()));
+        assert!(parsed.equals(&sid));
+
+        let bare = JsSid::parse(&sid.sid.to_string(), Some("usr".to_string())).unwrap();
+        assert_eq!(bare.labeled(), labeled);
+        assert!(JsSid::parse(&labeled, Some("team".to_string())).is_err());
+        assert!(JsSid::parse("usr_123", None).is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_uuid() {
+        let sid = JsSid::from_bytes(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16], None).unwrap();
+        assert_eq!(format_uuid(sid.sid.data()), "01020304-0506-0708-090a-0b0c0d0e0f10");
+        assert_eq!(parse_uuid("01020304-0506-0708-090a-0b0c0d0e0f10").unwrap(), *sid.sid.data());
+        assert_eq!(parse_uuid("0102030405060708090a0b0c0d0e0f10").unwrap(), *sid.sid.data());
+        assert!(parse_uuid("01020304-0506-0708-090a-0b0c0d0e0fxx").is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_short_and_compare() {
+        let a = JsSid::parse("usr_0da0fa0e02cssbhkanf04c_srb0", None).unwrap();
+        assert_eq!(a.short(), "usr_srb0");
+        let b = JsSid::new(None);
+        assert_eq!(JsSid::compare(&a, &b), -1);
+        assert_eq!(JsSid::compare(&b, &a), 1);
+        assert_eq!(JsSid::compare(&a, &a.clone()), 0);
+    }
+}